@@ -11,6 +11,10 @@ pub enum CommandError {
     IncorrectOperation(String),
     IncorrectOperand(Decimal),
     OperandIsMissing,
+    OutOfDomain(Operator, Decimal),
+    ComplexResult(Operator, Decimal),
+    DivisionByZero,
+    UnbalancedParenthesis,
     ParsingFailure {
         err: Option<ParseFloatError>
     },
@@ -31,6 +35,10 @@ impl ToString for CommandError {
                 "".into()
             },
             OperandIsMissing => "Second operand is missing".into(),
+            OutOfDomain(operator, operand) => format!("{} is undefined for {}", operator, operand),
+            ComplexResult(operator, operand) => format!("{} of {} is not a real number", operator, operand),
+            DivisionByZero => "Division by zero".into(),
+            UnbalancedParenthesis => "Closing parenthesis has no pair".into(),
             Overflow => "Overflow".into(),
         }
     }
@@ -0,0 +1,157 @@
+use std::fmt::{self, Display};
+
+use rust_decimal::Decimal;
+
+use super::{operand::Operand, CommandError};
+
+/// Binary formats of IEEE 754 the value is able to be stored in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    Single,
+    Double,
+}
+
+impl Precision {
+    pub const ALL: [Precision; 2] = [Precision::Single, Precision::Double];
+
+    pub fn exponent_bits(self) -> u32 {
+        match self {
+            Precision::Single => 8,
+            Precision::Double => 11,
+        }
+    }
+
+    /// Stored bits of the mantissa, the leading one is implicit
+    pub fn mantissa_bits(self) -> u32 {
+        match self {
+            Precision::Single => 23,
+            Precision::Double => 52,
+        }
+    }
+
+    pub fn bias(self) -> i32 {
+        (1 << (self.exponent_bits() - 1)) - 1
+    }
+}
+
+impl Display for Precision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Precision::Single => write!(f, "f32"),
+            Precision::Double => write!(f, "f64"),
+        }
+    }
+}
+
+/// How the operand is rounded to `f32` or `f64`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatInspection {
+    pub precision: Precision,
+    /// Bit pattern, `f32` takes the lowest 32 bits
+    pub bits: u64,
+    /// Value of the operand
+    pub exact: Decimal,
+    /// Value stored in the float, as far as `Decimal` is able to hold its digits
+    pub rounded: Decimal,
+}
+
+impl FloatInspection {
+    pub fn new(operand: &Operand, precision: Precision) -> Result<Self, CommandError> {
+        let exact: Decimal = operand.try_into()?;
+        let (bits, rounded): (u64, Option<Decimal>) = match precision {
+            Precision::Single => {
+                let value: f32 = operand.clone().try_into()?;
+                (value.to_bits().into(), Decimal::from_f32_retain(value))
+            },
+            Precision::Double => {
+                let value: f64 = operand.clone().try_into()?;
+                (value.to_bits(), Decimal::from_f64_retain(value))
+            },
+        };
+        Ok(Self {
+            precision,
+            bits,
+            exact,
+            // the float is infinite
+            rounded: rounded.ok_or(CommandError::Overflow)?,
+        })
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.bits >> (self.total_bits() - 1) == 1
+    }
+
+    /// Biased exponent as it is stored
+    pub fn exponent(&self) -> u64 {
+        let mask: u64 = (1 << self.precision.exponent_bits()) - 1;
+        (self.bits >> self.precision.mantissa_bits()) & mask
+    }
+
+    /// Power of two of the leading bit, subnormal numbers have the minimal one
+    pub fn unbiased_exponent(&self) -> i32 {
+        (self.exponent() as i32).max(1) - self.precision.bias()
+    }
+
+    pub fn mantissa(&self) -> u64 {
+        self.bits & ((1 << self.precision.mantissa_bits()) - 1)
+    }
+
+    /// Stored value minus the exact one, none if it is below the resolution of `Decimal`
+    pub fn error(&self) -> Option<Decimal> {
+        let error: Decimal = self.rounded - self.exact;
+        if error.is_zero() && !self.is_held_exactly() {
+            return None
+        }
+        Some(error)
+    }
+
+    /// Error as it is shown, `Decimal` has 28 decimal places at most
+    pub fn error_string(&self) -> String {
+        match self.error() {
+            Some(error) => error.normalize().to_string(),
+            None => "< 1e-28".into(),
+        }
+    }
+
+    pub fn hex(&self) -> String {
+        match self.precision {
+            Precision::Single => format!("{:08X}", self.bits),
+            Precision::Double => format!("{:016X}", self.bits),
+        }
+    }
+
+    /// Sign, exponent and mantissa bits separated by spaces
+    pub fn bits_string(&self) -> String {
+        let exponent_bits: usize = self.precision.exponent_bits() as usize;
+        let mantissa_bits: usize = self.precision.mantissa_bits() as usize;
+        format!("{} {:0exponent_bits$b} {:0mantissa_bits$b}",
+                self.is_negative() as u8,
+                self.exponent(),
+                self.mantissa())
+    }
+
+    /// The stored value fits `Decimal` without rounding
+    fn is_held_exactly(&self) -> bool {
+        let implicit_bit: u64 = if self.exponent() == 0 { 0 } else { 1 << self.precision.mantissa_bits() };
+        let significand: u64 = self.mantissa() | implicit_bit;
+        if significand == 0 {
+            return true
+        }
+        let trailing_zeros: u32 = significand.trailing_zeros();
+        let power_of_two: i32 = self.unbiased_exponent() 
+            - self.precision.mantissa_bits() as i32 
+            + trailing_zeros as i32;
+        if power_of_two >= 0 {
+            return true
+        }
+        // odd / 2^n = odd * 5^n / 10^n has n decimal places
+        let decimal_places: u32 = power_of_two.unsigned_abs();
+        let digits: Option<u128> = 5u128.checked_pow(decimal_places)
+            .and_then(|power| power.checked_mul((significand >> trailing_zeros).into()));
+        decimal_places <= 28 && matches!(digits, Some(digits) if digits < 1 << 96)
+    }
+
+    fn total_bits(&self) -> u32 {
+        1 + self.precision.exponent_bits() + self.precision.mantissa_bits()
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::VecDeque;
+
+use super::{State, Memory, PendingOperation, Nesting, settings::Settings};
+
+pub const DEFAULT_HISTORY_DEPTH: usize = 32;
+
+/// Everything a calculator action is able to change
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Snapshot {
+    pub(super) state: State,
+    pub(super) memory: Memory,
+    pub(super) pending: Vec<PendingOperation>,
+    pub(super) nesting: Vec<Nesting>,
+    /// Radix and digit capacity the operands are written in are restored with them
+    pub(super) settings: Settings,
+}
+
+/// Bounded undo and redo stacks of calculator snapshots
+#[derive(Debug)]
+pub struct History {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    depth: usize,
+}
+
+impl History {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            undo: VecDeque::with_capacity(depth),
+            redo: Vec::with_capacity(depth),
+            depth,
+        }
+    }
+
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        while self.undo.len() > depth {
+            self.undo.pop_front();
+        }
+        self.redo.truncate(depth);
+    }
+
+    /// Stores the snapshot taken before an action. Redo is impossible after it.
+    pub(super) fn record(&mut self, snapshot: Snapshot) {
+        self.redo.clear();
+        self.push_undo(snapshot);
+    }
+
+    pub(super) fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let previous: Snapshot = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    pub(super) fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let next: Snapshot = self.redo.pop()?;
+        self.push_undo(current);
+        Some(next)
+    }
+
+    fn push_undo(&mut self, snapshot: Snapshot) {
+        if self.depth == 0 {
+            return;
+        }
+        if self.undo.len() == self.depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(snapshot);
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_DEPTH)
+    }
+}
@@ -0,0 +1,294 @@
+//! Functions computed on `Decimal` with full precision.
+//! Trigonometry of `rust_decimal` loses about a half of the significant digits.
+
+use rust_decimal::{Decimal, MathematicalOps, prelude::ToPrimitive};
+use rust_decimal_macros::dec;
+
+// enough to turn a term of any series into zero
+const SERIES_MAX_TERMS: u32 = 100;
+// a value rounded to zero with these decimal points is considered to be zero
+const ZERO_DECIMAL_POINTS: u32 = 24;
+// the square of a greater number overflows
+const HUGE: Decimal = Decimal::from_parts(1_000_000_000, 0, 0, false, 0);
+// Stirling series is precise enough starting from this argument
+const STIRLING_MIN_ARGUMENT: Decimal = Decimal::from_parts(20, 0, 0, false, 0);
+// B(2k) / (2k (2k - 1)) as numerator and denominator
+const STIRLING_COEFFICIENTS: &[(i64, i64)] = &[(1, 12), (-1, 360), (1, 1260), (-1, 1680),
+                                               (1, 1188), (-691, 360360), (1, 156), (-3617, 122400)];
+// 1/3 typed as 0.33333333333333 still counts as a fraction
+const RATIONAL_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 12);
+const RATIONAL_MAX_DENOMINATOR: i64 = 10_000;
+
+/// Sine of an angle in radians
+pub fn sin(x: Decimal) -> Decimal {
+    let x: Decimal = reduce_angle(x);
+    let x_squared: Decimal = x * x;
+    let mut term: Decimal = x;
+    let mut sum: Decimal = x;
+    for n in 1..SERIES_MAX_TERMS {
+        term = -term * x_squared / Decimal::from((2 * n) * (2 * n + 1));
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+    }
+    sum
+}
+
+/// Cosine of an angle in radians
+pub fn cos(x: Decimal) -> Decimal {
+    sin(reduce_full_turn(x) + Decimal::HALF_PI)
+}
+
+/// Tangent of an angle in radians. Is absent where cosine turns into zero.
+pub fn tan(x: Decimal) -> Option<Decimal> {
+    let cos_x: Decimal = cos(x);
+    if cos_x.round_dp(ZERO_DECIMAL_POINTS).is_zero() {
+        return None
+    }
+    sin(x).checked_div(cos_x)
+}
+
+/// Arcsine in radians. Is absent outside of [-1, 1].
+pub fn asin(x: Decimal) -> Option<Decimal> {
+    if x.abs() > Decimal::ONE {
+        return None
+    }
+    if x.abs() == Decimal::ONE {
+        return Some(Decimal::HALF_PI * x)
+    }
+    let cos_y: Decimal = (Decimal::ONE - x * x).sqrt()?;
+    Some(atan(x / cos_y))
+}
+
+/// Arccosine in radians. Is absent outside of [-1, 1].
+pub fn acos(x: Decimal) -> Option<Decimal> {
+    asin(x).map(|asin_x| Decimal::HALF_PI - asin_x)
+}
+
+/// Arctangent in radians
+pub fn atan(x: Decimal) -> Decimal {
+    if x.abs() <= Decimal::ONE {
+        return atan_reduced(x)
+    }
+    // atan(x) = ±π/2 - atan(1/x)
+    let half_pi: Decimal = if x.is_sign_negative() {
+        -Decimal::HALF_PI
+    } else {
+        Decimal::HALF_PI
+    };
+    half_pi - atan_reduced(Decimal::ONE / x)
+}
+
+/// Arctangent of |x| <= 1
+fn atan_reduced(x: Decimal) -> Decimal {
+    // atan(x) = 2 * atan(x / (1 + sqrt(1 + x²))) 
+    // takes the argument closer to zero, where the series converges fast
+    const HALVINGS_COUNT: u32 = 3;
+    let mut x: Decimal = x;
+    for _ in 0..HALVINGS_COUNT {
+        let sqrt: Decimal = (Decimal::ONE + x * x).sqrt().unwrap();
+        x /= Decimal::ONE + sqrt;
+    }
+    let x_squared: Decimal = x * x;
+    let mut power: Decimal = x;
+    let mut sum: Decimal = x;
+    for n in 1..SERIES_MAX_TERMS {
+        power = -power * x_squared;
+        let term: Decimal = power / Decimal::from(2 * n + 1);
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+    }
+    sum * Decimal::from(2u32.pow(HALVINGS_COUNT))
+}
+
+/// Hyperbolic sine. Is absent on overflow.
+pub fn sinh(x: Decimal) -> Option<Decimal> {
+    if x.abs() >= Decimal::ONE {
+        // e^-x of a negative x loses the digits of the result
+        let exp_x: Decimal = exp(x.abs())?;
+        let sinh_abs_x: Decimal = (exp_x - Decimal::ONE.checked_div(exp_x)?) / Decimal::TWO;
+        return Some(with_sign_of(sinh_abs_x, x))
+    }
+    // the series does not lose the digits of a small x as the difference of exponents does
+    let x_squared: Decimal = x * x;
+    let mut term: Decimal = x;
+    let mut sum: Decimal = x;
+    for n in 1..SERIES_MAX_TERMS {
+        term = term * x_squared / Decimal::from((2 * n) * (2 * n + 1));
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+    }
+    Some(sum)
+}
+
+/// Hyperbolic cosine. Is absent on overflow.
+pub fn cosh(x: Decimal) -> Option<Decimal> {
+    let exp_x: Decimal = exp(x.abs())?;
+    Some((exp_x + Decimal::ONE / exp_x) / Decimal::TWO)
+}
+
+/// Hyperbolic tangent
+pub fn tanh(x: Decimal) -> Decimal {
+    // e^-2x does not affect the digits anymore
+    const SATURATION: Decimal = Decimal::from_parts(40, 0, 0, false, 0);
+    if x.abs() > SATURATION {
+        return with_sign_of(Decimal::ONE, x)
+    }
+    sinh(x).unwrap() / cosh(x).unwrap()
+}
+
+/// Inverse hyperbolic sine
+pub fn asinh(x: Decimal) -> Option<Decimal> {
+    let abs_x: Decimal = x.abs();
+    let result: Decimal = if abs_x > HUGE {
+        // x² overflows, but sqrt(x² + 1) is equal to x with all the digits
+        (abs_x * Decimal::TWO).checked_ln()?
+    } else {
+        (abs_x + (abs_x * abs_x + Decimal::ONE).sqrt()?).checked_ln()?
+    };
+    Some(with_sign_of(result, x))
+}
+
+/// Inverse hyperbolic cosine. Is absent for x < 1.
+pub fn acosh(x: Decimal) -> Option<Decimal> {
+    if x < Decimal::ONE {
+        return None
+    }
+    if x > HUGE {
+        return (x * Decimal::TWO).checked_ln()
+    }
+    (x + (x * x - Decimal::ONE).sqrt()?).checked_ln()
+}
+
+/// Inverse hyperbolic tangent. Is absent outside of (-1, 1).
+pub fn atanh(x: Decimal) -> Option<Decimal> {
+    if x.abs() >= Decimal::ONE {
+        return None
+    }
+    let ratio: Decimal = (Decimal::ONE + x) / (Decimal::ONE - x);
+    Some(ratio.checked_ln()? / Decimal::TWO)
+}
+
+/// Exponent with full precision. Is absent on overflow.
+/// `exp` of rust_decimal goes wrong already for x = 10.
+pub fn exp(x: Decimal) -> Option<Decimal> {
+    // e^x = e^integer * e^fraction
+    // e^-66 is less than the smallest Decimal
+    const UNDERFLOW: i64 = -66;
+    let integer: i64 = x.trunc().to_i64()?;
+    if integer < UNDERFLOW {
+        return Some(Decimal::ZERO)
+    }
+    let fraction: Decimal = x.fract();
+    let exp_integer: Decimal = if integer >= 0 {
+        Decimal::E.checked_powi(integer)?
+    } else {
+        Decimal::E_INVERSE.checked_powi(-integer)?
+    };
+    let mut term: Decimal = Decimal::ONE;
+    let mut exp_fraction: Decimal = Decimal::ONE;
+    for n in 1..SERIES_MAX_TERMS {
+        term = term * fraction / Decimal::from(n);
+        if term.is_zero() {
+            break;
+        }
+        exp_fraction += term;
+    }
+    exp_integer.checked_mul(exp_fraction)
+}
+
+/// Gamma function, Γ(n) = (n - 1)! for positive integers.
+/// Is absent for non-positive integers and on overflow.
+pub fn gamma(x: Decimal) -> Option<Decimal> {
+    if x <= Decimal::ZERO && x.fract().is_zero() {
+        return None
+    }
+    if x < Decimal::ONE / Decimal::TWO {
+        // reflection formula: Γ(x) Γ(1 - x) = π / sin(πx)
+        let sin: Decimal = sin(Decimal::PI * x);
+        // Γ(x) is below the resolution of `Decimal` when the denominator overflows
+        return match gamma(Decimal::ONE - x).and_then(|gamma| sin.checked_mul(gamma)) {
+            Some(denominator) => Decimal::PI.checked_div(denominator),
+            None => Some(Decimal::ZERO),
+        }
+    }
+    // Γ(x) = Γ(x + n) / (x (x + 1) ... (x + n - 1))
+    let mut shifted: Decimal = x;
+    let mut product: Decimal = Decimal::ONE;
+    while shifted < STIRLING_MIN_ARGUMENT {
+        product *= shifted;
+        shifted += Decimal::ONE;
+    }
+    exp(ln_gamma_stirling(shifted)?)?.checked_div(product)
+}
+
+/// Stirling series of ln Γ(x), converges fast for big x only
+fn ln_gamma_stirling(x: Decimal) -> Option<Decimal> {
+    // ln(2π) / 2
+    const HALF_LN_TWO_PI: Decimal = dec!(0.9189385332046727417803297364);
+    let mut series: Decimal = Decimal::ZERO;
+    let mut power: Decimal = x;
+    let x_squared: Decimal = x.checked_mul(x)?;
+    for &(numerator, denominator) in STIRLING_COEFFICIENTS {
+        series += Decimal::from(numerator) / Decimal::from(denominator) / power;
+        power = power.checked_mul(x_squared)?;
+    }
+    Some((x - Decimal::ONE / Decimal::TWO) * x.checked_ln()? - x + HALF_LN_TWO_PI + series)
+}
+
+/// Closest fraction `numerator / denominator` to `x`, found by continued fractions
+pub fn rational(x: Decimal) -> Option<(i64, i64)> {
+    let mut remainder: Decimal = x;
+    let (mut numerator, mut previous_numerator): (i64, i64) = (1, 0);
+    let (mut denominator, mut previous_denominator): (i64, i64) = (0, 1);
+    loop {
+        let integer: i64 = remainder.floor().to_i64()?;
+        (numerator, previous_numerator) = (integer.checked_mul(numerator)?.checked_add(previous_numerator)?, numerator);
+        (denominator, previous_denominator) = (integer.checked_mul(denominator)?.checked_add(previous_denominator)?, denominator);
+        if denominator > RATIONAL_MAX_DENOMINATOR {
+            return None
+        }
+        let error: Decimal = x - Decimal::from(numerator) / Decimal::from(denominator);
+        if error.abs() < RATIONAL_TOLERANCE {
+            return Some((numerator, denominator))
+        }
+        remainder = Decimal::ONE.checked_div(remainder - remainder.floor())?;
+    }
+}
+
+fn with_sign_of(value: Decimal, x: Decimal) -> Decimal {
+    if x.is_sign_negative() {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Brings an angle to [-π, π]
+fn reduce_full_turn(x: Decimal) -> Decimal {
+    let x: Decimal = x % Decimal::TWO_PI;
+    if x > Decimal::PI {
+        x - Decimal::TWO_PI
+    } else if x < -Decimal::PI {
+        x + Decimal::TWO_PI
+    } else {
+        x
+    }
+}
+
+/// Brings an angle to [-π/2, π/2] keeping its sine
+fn reduce_angle(x: Decimal) -> Decimal {
+    let x: Decimal = reduce_full_turn(x);
+    if x > Decimal::HALF_PI {
+        Decimal::PI - x
+    } else if x < -Decimal::HALF_PI {
+        -Decimal::PI - x
+    } else {
+        x
+    }
+}
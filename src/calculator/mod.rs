@@ -3,31 +3,47 @@ use std::cell::RefCell;
 use rust_decimal::Decimal;
 
 use rust_decimal_macros::dec;
+use serde::{Serialize, Deserialize};
 
-use self::{operand::Operand, operator::Operator, error::CommandError};
+use self::{operand::Operand, operator::Operator, error::CommandError, history::{History, Snapshot}, tape::Tape, settings::{Settings, Base, DigitCapacity}, float::{FloatInspection, Precision}};
 
 #[cfg(test)]
 mod tests;
 pub mod operand;
 pub mod operator;
 pub mod error;
+pub mod history;
+pub mod tape;
+pub mod settings;
+pub mod float;
+mod maths;
 
-pub const BUFFER_SIZE: usize = 16; // 8 
-// these both must be consistent
-const BUFFER_MAX_NUMBER_DEC: Decimal = dec!(9999_9999_9999_9999.0);
-// these both must be consistent
-const BUFFER_MAX_NUMBER: f64 = 9999_9999.0;
 const DOT_SYMBOL: char = '.';
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Calculator {
     pub state: State,
-    memory: Memory
+    memory: Memory,
+    #[serde(skip)]
+    history: History,
+    #[serde(default)]
+    tape: Tape,
+    #[serde(default)]
+    settings: Settings,
+    /// Operations of the algebraic mode waiting for the ones of higher precedence
+    #[serde(default)]
+    pending: Vec<PendingOperation>,
+    /// Calculations interrupted by the open parentheses, the innermost is the last
+    #[serde(default)]
+    nesting: Vec<Nesting>,
+    /// Number of changes, the session isn't saved unless it has grown
+    #[serde(skip)]
+    revision: u64,
 }
 
 pub type OperandCell = RefCell<Option<Operand>>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum State {
     ReadingLeftOrOperator(OperandCell),
     ReadingRight {
@@ -39,16 +55,72 @@ pub enum State {
         operator: Operator,
         right: OperandCell
     },
-    Result(OperandCell)
+    Result(OperandCell, Option<Constant>)
 }
 
 impl State {
     pub fn begin() -> Self {
         Self::ReadingLeftOrOperator(cell_with_operand(Operand::new(), None))
     }
+
+    pub fn begin_in(settings: &Settings) -> Self {
+        Self::ReadingLeftOrOperator(cell_with_operand(Operand::with_settings(settings), None))
+    }
+
+    fn operand_cells(&self) -> Vec<&OperandCell> {
+        match self {
+            State::ReadingLeftOrOperator(operand_cell) 
+            | State::ReadingRight { left: operand_cell, .. } 
+            | State::Result(operand_cell, _) => vec![operand_cell],
+            State::ReadingRightOrNextAction { left, right, .. } => vec![left, right],
+        }
+    }
+}
+
+/// Operation repeated by every '=' pressed after the result
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Constant {
+    pub operator: Operator,
+    pub operand: Decimal,
+    /// The constant stands on the left side of the operator
+    pub is_left: bool,
+}
+
+impl Constant {
+    fn new(operator: Operator, left: Decimal, right: Decimal, k_constant: bool) -> Self {
+        let is_left: bool = k_constant 
+            && matches!(operator, Operator::Multiplication | Operator::Division);
+        Self {
+            operator,
+            operand: if is_left { left } else { right },
+            is_left,
+        }
+    }
+
+    fn operands(&self, current: Decimal) -> (Decimal, Decimal) {
+        if self.is_left {
+            (self.operand, current)
+        } else {
+            (current, self.operand)
+        }
+    }
+}
+
+/// Left operand and operator put aside until the right operand is calculated
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PendingOperation {
+    pub left: Decimal,
+    pub operator: Operator,
+}
+
+/// Calculation outside of parentheses, resumed when they are closed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Nesting {
+    state: State,
+    pending: Vec<PendingOperation>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 struct Memory {
     value: Decimal
 }
@@ -62,15 +134,39 @@ impl Calculator {
     fn with_state(state: State) -> Self {
         Self { 
             state, 
-            memory: Default::default() 
+            memory: Default::default(),
+            history: Default::default(),
+            tape: Default::default(),
+            settings: Default::default(),
+            pending: Vec::new(),
+            nesting: Vec::new(),
+            revision: 0,
         }
     }
 
     pub fn symbol_in(&mut self, symbol: char) -> Result<&mut Self, CommandError> {
+        self.recorded(|calc| calc.read_symbol(symbol).map(|_| ()))?;
+        Ok(self)
+    }
+
+    fn read_symbol(&mut self, symbol: char) -> Result<&mut Self, CommandError> {
+        match symbol {
+            '(' => return self.open_parenthesis(),
+            ')' => return self.close_parenthesis(),
+            _ => {}
+        }
+        if is_eq(symbol) {
+            // closes all the parentheses left open
+            while !self.nesting.is_empty() {
+                self.close_parenthesis()?;
+            }
+        }
         if is_operation(symbol) {
-            return self.operator_in(Operator::try_from(symbol)?)
+            return self.read_operator(Operator::try_from(symbol)?)
         }
         match &mut self.state {
+            // nothing to calculate, e.g. after the parentheses are closed
+            State::ReadingLeftOrOperator(_) if is_eq(symbol) => Ok(self),
             State::ReadingLeftOrOperator(operand_cell) => {
                 let mut operand: Operand = operand_cell.take().unwrap();
                 let operand_should_be_replaced: bool = operand.should_reset_on_clear();
@@ -84,7 +180,7 @@ impl Calculator {
                 Ok(self)
             },
             State::ReadingRight { left, operator } => {
-                let mut right = Operand::new();
+                let mut right = Operand::with_settings(&self.settings);
                 // maybe, it is worth to fix with checking what symbol is sent
                 right.send_symbol(symbol);
                 let reset_operand_on_erase = Some(false);
@@ -103,7 +199,8 @@ impl Calculator {
                 // await /, *, +, - or =
                 let left: Operand = left.take().unwrap();
                 let mut right: Operand = right.take().unwrap();
-                if is_number(symbol) || symbol == '.' {
+                // letters are digits in hexadecimal
+                if symbol.is_ascii_hexdigit() || symbol == '.' {
                     let operand_should_be_replaced: bool = right.should_reset_on_clear();
                     if operand_should_be_replaced {
                         right.send_erase();
@@ -120,11 +217,16 @@ impl Calculator {
                 if is_eq(symbol) {
                     let left: Decimal = left.try_into()?;
                     let right: Decimal = right.try_into()?;
-                    let result: Decimal = operator.apply(left, Some(right))?;
-                    let result: Operand = Operand::try_from(result)?;
+                    let operator: Operator = *operator;
+                    let result: Decimal = operator.apply(left, Some(right), &self.settings)?;
+                    self.tape.record(left, operator, Some(right), result);
+                    let result: Decimal = self.reduce_pending(result, None)?;
+                    let result: Operand = Operand::from_dec(result, &self.settings)?;
                     let reset_operand_on_erase = Some(true);
+                    let constant = Constant::new(operator, left, right, self.settings.k_constant);
                     self.state = State::Result(
-                        cell_with_operand(result, reset_operand_on_erase));
+                        cell_with_operand(result, reset_operand_on_erase), 
+                        Some(constant));
                     return Ok(self)
                 }
                 self.state = State::ReadingRightOrNextAction { 
@@ -134,24 +236,47 @@ impl Calculator {
                 };
                 Ok(self)
             },
-            State::Result(_) => {
-                self.state = State::begin();
-                self.symbol_in(symbol)
+            State::Result(operand_cell, Some(constant)) if is_eq(symbol) => {
+                let constant: Constant = *constant;
+                // the operand stays if the repetition fails
+                let operand: Operand = operand_cell.borrow().clone().unwrap();
+                let (left, right) = constant.operands(operand.try_into()?);
+                let result: Decimal = constant.operator.apply(left, Some(right), &self.settings)?;
+                self.tape.record(left, constant.operator, Some(right), result);
+                let result: Operand = Operand::from_dec(result, &self.settings)?;
+                let reset_operand_on_erase = Some(true);
+                self.state = State::Result(
+                    cell_with_operand(result, reset_operand_on_erase), 
+                    Some(constant));
+                Ok(self)
+            },
+            State::Result(_, None) if is_eq(symbol) => Ok(self),
+            State::Result(..) => {
+                self.state = State::begin_in(&self.settings);
+                self.read_symbol(symbol)
             }
         }
     }
 
     pub fn operator_in(&mut self, operator: Operator) -> Result<&mut Self, CommandError> {
+        self.recorded(|calc| calc.read_operator(operator).map(|_| ()))?;
+        Ok(self)
+    }
+
+    fn read_operator(&mut self, operator: Operator) -> Result<&mut Self, CommandError> {
         match &mut self.state {
             State::ReadingLeftOrOperator(operand_cell) => {
                 let operand: Operand = operand_cell.take().unwrap();
                 let reset_operand_on_erase = Some(true);
                 if operator.is_unary() {
-                    let result: Decimal = operator.apply(operand.try_into()?, None)?;
-                    let result: Operand = Operand::try_from(result)?;
-                    self.state = State::Result(cell_with_operand(result, reset_operand_on_erase));
+                    let operand: Decimal = operand.try_into()?;
+                    let result: Decimal = operator.apply(operand, None, &self.settings)?;
+                    self.tape.record(operand, operator, None, result);
+                    let result: Operand = Operand::from_dec(result, &self.settings)?;
+                    self.state = State::Result(cell_with_operand(result, reset_operand_on_erase), None);
                     return Ok(self)
                 }
+                self.pending.clear();
                 self.state = State::ReadingRight { 
                     left: cell_with_operand(operand, None), 
                     operator 
@@ -165,15 +290,27 @@ impl Calculator {
                 if operator.is_unary() {
                     let operand: Operand = operand_cell.take().unwrap();
                     let operand: Decimal = operand.try_into()?;
-                    let result: Decimal = operator.apply(operand, None)?;
-                    let result = Operand::try_from(result)?;
+                    let result: Decimal = operator.apply(operand, None, &self.settings)?;
+                    self.tape.record(operand, operator, None, result);
+                    let result = Operand::from_dec(result, &self.settings)?;
                     let reset_operand_on_erase = Some(true);
                     self.state = State::Result(
-                        cell_with_operand(result, reset_operand_on_erase));
+                        cell_with_operand(result, reset_operand_on_erase), None);
+                    return Ok(self)
+                }
+                if !self.pending.is_empty() {
+                    // the replaced operator may have lower precedence than the pending ones
+                    let left: Decimal = operand_cell.take().unwrap().try_into()?;
+                    let left: Decimal = self.reduce_pending(left, Some(operator))?;
+                    let left: Operand = Operand::from_dec(left, &self.settings)?;
+                    self.state = State::ReadingRight { 
+                        left: cell_with_operand(left, None), 
+                        operator 
+                    };
                     return Ok(self)
                 }
                 self.state = State::ReadingLeftOrOperator(cell_move(operand_cell));
-                self.operator_in(operator)
+                self.read_operator(operator)
             },
             State::ReadingRightOrNextAction { 
                 left, 
@@ -183,8 +320,9 @@ impl Calculator {
                 let right: Operand = right.take().unwrap();
                 let right: Decimal = right.try_into()?;
                 if operator.is_unary() {
-                    let result: Decimal = operator.apply(right, None)?;
-                    let result: Operand = Operand::try_from(result)?;
+                    let result: Decimal = operator.apply(right, None, &self.settings)?;
+                    self.tape.record(right, operator, None, result);
+                    let result: Operand = Operand::from_dec(result, &self.settings)?;
                     let first_operator: Operator = *first_operator;
                     let reset_operand_on_erase = Some(true);
                     self.state = State::ReadingRightOrNextAction { 
@@ -196,25 +334,39 @@ impl Calculator {
                 }
                 let left: Operand = left.take().unwrap();
                 let left: Decimal = left.try_into()?;
-                let result: Decimal = first_operator.apply(left, Some(right))?;
-                let result: Operand = Operand::try_from(result)?;
+                let first_operator: Operator = *first_operator;
+                if self.settings.algebraic && !applies_before(first_operator, operator) {
+                    self.pending.push(PendingOperation { left, operator: first_operator });
+                    let right: Operand = Operand::from_dec(right, &self.settings)?;
+                    self.state = State::ReadingRight { 
+                        left: cell_with_operand(right, None), 
+                        operator 
+                    };
+                    return Ok(self)
+                }
+                let result: Decimal = first_operator.apply(left, Some(right), &self.settings)?;
+                self.tape.record(left, first_operator, Some(right), result);
+                let result: Decimal = self.reduce_pending(result, Some(operator))?;
+                let result: Operand = Operand::from_dec(result, &self.settings)?;
                 self.state = State::ReadingRight { 
                     left: cell_with_operand(result, None), 
                     operator 
                 };
                 Ok(self)
             },
-            State::Result(operand_cell) => {
+            State::Result(operand_cell, _) => {
                 let operand: Operand = operand_cell.take().unwrap();
                 if operator.is_unary() {
                     let operand: Decimal = operand.try_into()?;
-                    let result: Decimal = operator.apply(operand, None)?;
-                    let result: Operand = Operand::try_from(result)?;
+                    let result: Decimal = operator.apply(operand, None, &self.settings)?;
+                    self.tape.record(operand, operator, None, result);
+                    let result: Operand = Operand::from_dec(result, &self.settings)?;
                     let reset_operand_on_erase = Some(true);
                     self.state = State::Result(
-                        cell_with_operand(result, reset_operand_on_erase));
+                        cell_with_operand(result, reset_operand_on_erase), None);
                     return Ok(self)
                 }
+                self.pending.clear();
                 self.state = State::ReadingRight { 
                     left: cell_with_operand(operand, None), 
                     operator
@@ -224,7 +376,63 @@ impl Calculator {
         }
     }
 
-    fn current_operand_to_dec(&self) -> Decimal {
+    fn open_parenthesis(&mut self) -> Result<&mut Self, CommandError> {
+        let outer = Nesting {
+            state: std::mem::replace(&mut self.state, State::begin_in(&self.settings)),
+            pending: std::mem::take(&mut self.pending),
+        };
+        self.nesting.push(outer);
+        Ok(self)
+    }
+
+    /// The value in parentheses becomes the current operand of the outer calculation
+    fn close_parenthesis(&mut self) -> Result<&mut Self, CommandError> {
+        let outer: Nesting = self.nesting.pop()
+            .ok_or(CommandError::UnbalancedParenthesis)?;
+        let value: Decimal = match &self.state {
+            State::ReadingRightOrNextAction { left, operator, right } => {
+                let left: Decimal = cell_ref_to_dec(left);
+                let right: Decimal = cell_ref_to_dec(right);
+                let operator: Operator = *operator;
+                let result: Decimal = operator.apply(left, Some(right), &self.settings)?;
+                self.tape.record(left, operator, Some(right), result);
+                self.reduce_pending(result, None)?
+            },
+            _ => {
+                let value: Decimal = self.current_operand_to_dec();
+                self.reduce_pending(value, None)?
+            }
+        };
+        // fails here instead of panicking in the replacement
+        Operand::from_dec(value, &self.settings)?;
+        self.state = outer.state;
+        self.pending = outer.pending;
+        self.replace_current_operand(value)?;
+        Ok(self)
+    }
+
+    /// Number of parentheses open
+    pub fn nesting_depth(&self) -> usize {
+        self.nesting.len()
+    }
+
+    /// Applies the pending operations which bind tighter than `next`, all of them if there is none
+    fn reduce_pending(&mut self, 
+                      mut right: Decimal, 
+                      next: Option<Operator>) -> Result<Decimal, CommandError> {
+        while let Some(&pending) = self.pending.last() {
+            if matches!(next, Some(next) if !applies_before(pending.operator, next)) {
+                break;
+            }
+            self.pending.pop();
+            let result: Decimal = pending.operator.apply(pending.left, Some(right), &self.settings)?;
+            self.tape.record(pending.left, pending.operator, Some(right), result);
+            right = result;
+        }
+        Ok(right)
+    }
+
+    pub fn current_operand_to_dec(&self) -> Decimal {
         match &self.state {
             State::ReadingLeftOrOperator(operand_cell) 
                 => cell_ref_to_dec(operand_cell),
@@ -237,11 +445,30 @@ impl Calculator {
                 operator: _, 
                 right: operand_cell 
             } => cell_ref_to_dec(operand_cell),
-            State::Result(operand_cell)
+            State::Result(operand_cell, _)
                 => cell_ref_to_dec(operand_cell),
         }
     }
 
+    /// Shows how the current operand is stored in `f32` or `f64`
+    pub fn inspect_float(&self, precision: Precision) -> Result<FloatInspection, CommandError> {
+        match &self.state {
+            State::ReadingLeftOrOperator(operand_cell) 
+                => cell_ref_to_float(operand_cell, precision),
+            State::ReadingRight { 
+                left: operand_cell, 
+                operator: _ 
+            } => cell_ref_to_float(operand_cell, precision),
+            State::ReadingRightOrNextAction { 
+                left: _, 
+                operator: _, 
+                right: operand_cell 
+            } => cell_ref_to_float(operand_cell, precision),
+            State::Result(operand_cell, _)
+                => cell_ref_to_float(operand_cell, precision),
+        }
+    }
+
     pub fn current_operand_to_str(&self) -> String {
         match &self.state {
             State::ReadingLeftOrOperator(operand_cell) 
@@ -255,13 +482,18 @@ impl Calculator {
                 operator: _, 
                 right: operand_cell 
             } => cell_ref_to_str(operand_cell),
-            State::Result(operand_cell)
+            State::Result(operand_cell, _)
                 => cell_ref_to_str(operand_cell),
         }
     }
 
-    pub fn set_current_operand(&mut self, value: Decimal) {
-        let operand = Operand::try_from(value).unwrap(); ////
+    pub fn set_current_operand(&mut self, value: Decimal) -> Result<(), CommandError> {
+        self.recorded(|calc| calc.replace_current_operand(value))
+    }
+
+    /// Fails if the value doesn't fit the display, the state is kept then
+    fn replace_current_operand(&mut self, value: Decimal) -> Result<(), CommandError> {
+        let operand = Operand::from_dec(value, &self.settings)?;
         let reset_operand_on_erase = Some(true);
         let new_cell: OperandCell = cell_with_operand(operand, reset_operand_on_erase);
         match &mut self.state {
@@ -284,37 +516,44 @@ impl Calculator {
                 operator: *operator, 
                 right: new_cell 
             },
-            State::Result(_)
-                => self.state = State::Result(new_cell),
+            State::Result(_, constant)
+                => self.state = State::Result(new_cell, *constant),
         }
-        ;
+        Ok(())
     }
 
     pub fn memory_add(&mut self) -> Result<(), CommandError> {
-        self.memory_apply(Operator::Addition)
+        self.recorded(|calc| calc.memory_apply(Operator::Addition))
     }
 
     pub fn memory_sub(&mut self) -> Result<(), CommandError> {
-        self.memory_apply(Operator::Subtraction)
+        self.recorded(|calc| calc.memory_apply(Operator::Subtraction))
     }
 
     fn memory_apply(&mut self, operator: Operator) -> Result<(), CommandError> {
         let current_operand_value: Decimal = self.current_operand_to_dec();
-        self.memory.value = operator.apply(current_operand_value, Some(self.memory.value))?;
+        self.memory.value = operator.apply(current_operand_value, 
+                                           Some(self.memory.value), 
+                                           &self.settings)?;
         Ok(())
     }
 
     pub fn memory_mrc(&mut self) -> Result<(), CommandError> {
-        let current_operand_value: Decimal = self.current_operand_to_dec();
-        if current_operand_value == self.memory.value {
-            self.memory.value = Decimal::ZERO;
-            return Ok(())
-        }
-        self.set_current_operand(self.memory.value);
-        Ok(())
+        self.recorded(|calc| {
+            let current_operand_value: Decimal = calc.current_operand_to_dec();
+            if current_operand_value == calc.memory.value {
+                calc.memory.value = Decimal::ZERO;
+                return Ok(())
+            }
+            calc.replace_current_operand(calc.memory.value)
+        })
     }
 
     pub fn percentage(&mut self) -> Result<(), CommandError> {
+        self.recorded(Self::apply_percentage)
+    }
+
+    fn apply_percentage(&mut self) -> Result<(), CommandError> {
         match &self.state {
             State::ReadingLeftOrOperator(_) => {},
             State::ReadingRight { .. } => {},
@@ -327,6 +566,7 @@ impl Calculator {
                 let right: Operand = right.take().unwrap();
                 let left_dec: Decimal = left.try_into()?;
                 let right_dec: Decimal = right.try_into()?;
+                let operator: Operator = *operator;
                 match operator {
                     // Operator::Division => todo!(),
                     Operator::Addition => {
@@ -334,50 +574,107 @@ impl Calculator {
                         let result: Decimal = left_dec.checked_add(percent)
                             .ok_or(CommandError::Overflow)?
                             .normalize();
-                        let result: Operand = Operand::try_from(result)?;
+                        self.tape.record_percentage(left_dec, operator, right_dec, result);
+                        let result: Decimal = self.reduce_pending(result, None)?;
+                        let result: Operand = Operand::from_dec(result, &self.settings)?;
                         let reset_operand_on_erase = Some(true);
                         self.state = State::Result(
-                            cell_with_operand(result, reset_operand_on_erase))
+                            cell_with_operand(result, reset_operand_on_erase), None)
                     },
                     Operator::Multiplication => {
                         let percent: Decimal = checked_percent(left_dec, right_dec)?.normalize();
-                        let percent: Operand = Operand::try_from(percent)?;
+                        self.tape.record_percentage(left_dec, operator, right_dec, percent);
+                        let percent: Decimal = self.reduce_pending(percent, None)?;
+                        let percent: Operand = Operand::from_dec(percent, &self.settings)?;
                         let reset_operand_on_erase = Some(true);
                         self.state = State::Result(
-                            cell_with_operand(percent, reset_operand_on_erase))
+                            cell_with_operand(percent, reset_operand_on_erase), None)
                     },
                     Operator::Subtraction => {
                         let percent: Decimal = checked_percent(left_dec, right_dec)?;
                         let result: Decimal = left_dec.checked_sub(percent)
                             .ok_or(CommandError::Overflow)?
                             .normalize();
-                        let result: Operand = Operand::try_from(result)?;
+                        self.tape.record_percentage(left_dec, operator, right_dec, result);
+                        let result: Decimal = self.reduce_pending(result, None)?;
+                        let result: Operand = Operand::from_dec(result, &self.settings)?;
                         let reset_operand_on_erase = Some(true);
                         self.state = State::Result(
-                            cell_with_operand(result, reset_operand_on_erase))
+                            cell_with_operand(result, reset_operand_on_erase), None)
                     },
                     _ => {}
                 }
             },
-            State::Result(_) => {},
+            State::Result(..) => {},
         }
         Ok(())
     }
 
-    pub fn pi(&mut self) {
-        self.set_current_operand(Decimal::PI);
+    pub fn toggle_sign(&mut self) {
+        // negation never fails
+        let _ = self.recorded(|calc| {
+            calc.negate_current_operand();
+            Ok(())
+        });
+    }
+
+    fn negate_current_operand(&mut self) {
+        match &mut self.state {
+            State::ReadingRight { left, operator } => {
+                // the displayed left operand becomes the negated right one
+                let mut right: Operand = left.borrow().clone().unwrap();
+                right.negate();
+                let reset_operand_on_erase = Some(true);
+                self.state = State::ReadingRightOrNextAction { 
+                    left: cell_move(left), 
+                    operator: *operator, 
+                    right: cell_with_operand(right, reset_operand_on_erase) 
+                };
+            },
+            State::ReadingLeftOrOperator(operand_cell) 
+            | State::ReadingRightOrNextAction { right: operand_cell, .. } 
+            | State::Result(operand_cell, _) => {
+                let mut operand_ref = operand_cell.borrow_mut();
+                let operand: &mut Operand = operand_ref.as_mut().unwrap();
+                operand.negate();
+            },
+        }
+    }
+
+    pub fn pi(&mut self) -> Result<(), CommandError> {
+        self.set_current_operand(Decimal::PI)
     }
 
-    pub fn eulers_number(&mut self) {
-        self.set_current_operand(Decimal::E);
+    pub fn eulers_number(&mut self) -> Result<(), CommandError> {
+        self.set_current_operand(Decimal::E)
     }
 
     pub fn erase_all(&mut self) -> &mut Self {
-        self.state = State::begin();
+        // erasing never fails
+        let _ = self.recorded(|calc| {
+            calc.reset();
+            Ok(())
+        });
+        self
+    }
+
+    fn reset(&mut self) -> &mut Self {
+        self.state = State::begin_in(&self.settings);
+        self.pending.clear();
+        self.nesting.clear();
         self
     }
 
     pub fn erase(&mut self) -> &mut Self {
+        // erasing never fails
+        let _ = self.recorded(|calc| {
+            calc.erase_current();
+            Ok(())
+        });
+        self
+    }
+
+    fn erase_current(&mut self) -> &mut Self {
         match &mut self.state {
             State::ReadingLeftOrOperator(operand_cell) => {
                 let mut operand: Operand = operand_cell.take().unwrap();
@@ -385,19 +682,210 @@ impl Calculator {
                 operand_cell.replace(Some(operand));
                 self
             },
-            State::ReadingRight { .. } => self.erase_all(),
+            State::ReadingRight { .. } => self.reset(),
             State::ReadingRightOrNextAction { right: right_cell, .. } => {
                 let mut operand: Operand = right_cell.take().unwrap();
                 operand.send_erase();
                 right_cell.replace(Some(operand));
                 self
             },
-            State::Result(_) => {
-                self.state = State::begin();
+            State::Result(..) => {
+                self.state = State::begin_in(&self.settings);
                 self
             }
         }
     }
+
+    /// Restores the state preceding the last action. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let current: Snapshot = self.snapshot();
+        match self.history.undo(current) {
+            Some(previous) => {
+                self.restore(previous);
+                self.revision += 1;
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Brings back the state the last undo has discarded. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let current: Snapshot = self.snapshot();
+        match self.history.redo(current) {
+            Some(next) => {
+                self.restore(next);
+                self.revision += 1;
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn tape(&self) -> &Tape {
+        &self.tape
+    }
+
+    pub fn clear_tape(&mut self) {
+        self.tape.clear();
+        self.revision += 1;
+    }
+
+    /// Grows with every change of the calculator
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn toggle_k_constant(&mut self) {
+        self.settings.k_constant = !self.settings.k_constant;
+        self.revision += 1;
+    }
+
+    /// Switches degrees, radians and grads in turn
+    pub fn next_angle_unit(&mut self) {
+        self.settings.angle_unit = self.settings.angle_unit.next();
+        self.revision += 1;
+    }
+
+    /// Switches between immediate execution and the precedence of operators
+    pub fn toggle_algebraic(&mut self) {
+        self.settings.algebraic = !self.settings.algebraic;
+        self.revision += 1;
+    }
+
+    /// Switches between the programmer mode and the regular one,
+    /// fractional parts of the operands are lost on the way to the programmer mode
+    pub fn toggle_programmer(&mut self) -> Result<(), CommandError> {
+        self.convert_operands(Settings {
+            programmer: !self.settings.programmer,
+            ..self.settings
+        })
+    }
+
+    /// Base of the programmer mode
+    pub fn set_base(&mut self, base: Base) -> Result<(), CommandError> {
+        self.convert_operands(Settings {
+            base,
+            ..self.settings
+        })
+    }
+
+    /// Switches 8, 16, 32 and 64 bits in turn, the operands are wrapped into the new word
+    pub fn next_word_size(&mut self) -> Result<(), CommandError> {
+        self.convert_operands(Settings {
+            word_size: self.settings.word_size.next(),
+            ..self.settings
+        })
+    }
+
+    /// Switches the signed and unsigned interpretation of the bits
+    pub fn toggle_unsigned(&mut self) -> Result<(), CommandError> {
+        self.convert_operands(Settings {
+            unsigned: !self.settings.unsigned,
+            ..self.settings
+        })
+    }
+
+    /// Digits of the display, 8 to 28 of them
+    pub fn set_digit_capacity(&mut self, digits: usize) -> Result<(), CommandError> {
+        let digits: DigitCapacity = DigitCapacity::try_from(digits)
+            .map_err(CommandError::IncorrectOperation)?;
+        self.convert_operands(Settings { digits, ..self.settings })
+    }
+
+    /// Switches the digit capacities in turn
+    pub fn next_digit_capacity(&mut self) -> Result<(), CommandError> {
+        self.convert_operands(Settings {
+            digits: self.settings.digits.next(),
+            ..self.settings
+        })
+    }
+
+    /// Rewrites the operands and the memory in the radix and digit capacity of the new settings.
+    /// Nothing is changed if any of them doesn't fit. The conversion is undoable.
+    fn convert_operands(&mut self, settings: Settings) -> Result<(), CommandError> {
+        self.recorded(|calc| {
+            let states = std::iter::once(&calc.state)
+                .chain(calc.nesting.iter().map(|nesting| &nesting.state));
+            let mut converted: Vec<(&OperandCell, Operand)> = Vec::new();
+            for state in states {
+                for operand_cell in state.operand_cells() {
+                    let value: Decimal = cell_ref_to_dec(operand_cell);
+                    converted.push((operand_cell, Operand::from_dec(value, &settings)?));
+                }
+            }
+            let memory: Decimal = (&Operand::from_dec(calc.memory.value, &settings)?).try_into()?;
+            for (operand_cell, operand) in converted {
+                let reset_operand_on_erase = Some(true);
+                operand_cell.replace(cell_with_operand(operand, reset_operand_on_erase).into_inner());
+            }
+            calc.memory.value = memory;
+            calc.settings = settings;
+            Ok(())
+        })
+    }
+
+    pub fn toggle_division_rounding(&mut self) {
+        self.settings.division_rounding = self.settings.division_rounding.toggle();
+        self.revision += 1;
+    }
+
+    pub fn set_history_depth(&mut self, depth: usize) {
+        self.history.set_depth(depth);
+    }
+
+    /// Failed actions leave the calculator and the tape as they were, 
+    /// neither they nor the ones changing nothing are undoable
+    fn recorded<T>(&mut self, 
+                   action: impl FnOnce(&mut Self) -> Result<T, CommandError>) -> Result<T, CommandError> {
+        let snapshot: Snapshot = self.snapshot();
+        let response: Result<T, CommandError> = action(self);
+        match response {
+            Ok(_) => {
+                let calculated: bool = self.tape.commit();
+                let changed: bool = snapshot != self.snapshot();
+                if changed {
+                    self.history.record(snapshot);
+                }
+                if changed || calculated {
+                    self.revision += 1;
+                }
+            },
+            Err(_) => {
+                self.tape.discard();
+                self.restore(snapshot);
+            },
+        }
+        response
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            state: self.state.clone(),
+            memory: self.memory.clone(),
+            pending: self.pending.clone(),
+            nesting: self.nesting.clone(),
+            settings: self.settings,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.state = snapshot.state;
+        self.memory = snapshot.memory;
+        self.pending = snapshot.pending;
+        self.nesting = snapshot.nesting;
+        self.settings = self.settings.with_format_of(snapshot.settings);
+    }
+}
+
+/// `first` operator typed before `next` is applied first if it binds at least as tight
+fn applies_before(first: Operator, next: Operator) -> bool {
+    first.precedence() > next.precedence()
+        || first.precedence() == next.precedence() && !next.is_right_associative()
 }
 
 fn checked_percent(number: Decimal, percent: Decimal) -> Result<Decimal, CommandError> {
@@ -426,6 +914,13 @@ fn cell_ref_to_dec(cell_ref: &OperandCell) -> Decimal {
         .unwrap()
 }
 
+fn cell_ref_to_float(cell_ref: &OperandCell, precision: Precision) -> Result<FloatInspection, CommandError> {
+    let operand_ref = cell_ref.borrow();
+    let operand: &Operand = operand_ref.as_ref()
+        .ok_or(CommandError::OperandIsMissing)?;
+    FloatInspection::new(operand, precision)
+}
+
 fn cell_ref_to_str(cell_ref: &OperandCell) -> String {
     cell_ref.borrow()
         .as_ref()
@@ -434,7 +929,7 @@ fn cell_ref_to_str(cell_ref: &OperandCell) -> String {
 }
 
 fn is_operation(symbol: char) -> bool {
-    const OPERATIONS: &[char] = &['/', '+', '-', '*', '^'];
+    const OPERATIONS: &[char] = &['/', '+', '-', '*', '^', 'm', '\\', '&', '|', '<', '>'];
     OPERATIONS.contains(&symbol)
 }
 
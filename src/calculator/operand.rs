@@ -1,17 +1,24 @@
-use rust_decimal::{Decimal, prelude::FromPrimitive};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use serde::{Serialize, Deserialize};
 
-use super::{is_number, BUFFER_SIZE, DOT_SYMBOL, CommandError, BUFFER_MAX_NUMBER_DEC, BUFFER_MAX_NUMBER};
+use super::{is_number, DOT_SYMBOL, CommandError, settings::{Base, Radix, Settings, DigitCapacity}};
 use std::str::FromStr;
 
 const BUFFER_SIZE_INTERN: usize = 28 + 1;
 
 /// Implements operand representation and conversion to current view for calculator
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Operand {
     buffer: String,
     dot_is_after: Option<usize>,
     is_negative: bool,
     reset_on_clear: bool,
+    /// Format of the integer typed in the programmer mode
+    #[serde(default)]
+    radix: Option<Radix>,
+    /// Digits of the display outside of the programmer mode
+    #[serde(default)]
+    digits: DigitCapacity,
 }
 
 impl Operand {
@@ -19,29 +26,120 @@ impl Operand {
         Default::default()
     }
 
+    /// Empty operand of the current mode
+    pub(super) fn with_settings(settings: &Settings) -> Self {
+        Self::with_format(settings.radix(), settings.digits)
+    }
+
+    fn with_format(radix: Option<Radix>, digits: DigitCapacity) -> Self {
+        Self {
+            radix,
+            digits,
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn from_dec(value: Decimal, settings: &Settings) -> Result<Self, CommandError> {
+        Self::from_dec_in(value, settings.radix(), settings.digits)
+    }
+
+    /// Fractional part is dropped in the programmer mode and the integer is wrapped
+    /// into the word. It is written with its bit pattern in all bases but decimal.
+    fn from_dec_in(value: Decimal, 
+                   radix: Option<Radix>, 
+                   digits: DigitCapacity) -> Result<Self, CommandError> {
+        let radix: Radix = match radix {
+            Some(radix) => radix,
+            None => return Self::from_real(value, digits)
+        };
+        let integer: i128 = value.trunc()
+            .to_i128()
+            .ok_or(CommandError::IncorrectOperand(value))?;
+        let integer: i128 = radix.wrap(integer);
+        let mut operand = Self::with_format(Some(radix), digits);
+        if radix.base == Base::Decimal {
+            operand.buffer = radix.base.format(integer.unsigned_abs());
+            operand.is_negative = integer < 0;
+        } else {
+            operand.buffer = radix.base.format(radix.bit_pattern(integer).into());
+        }
+        Ok(operand)
+    }
+
+    fn from_real(value: Decimal, digits: DigitCapacity) -> Result<Self, CommandError> {
+        if value.trunc().abs() > digits.max_number() {
+            Err(CommandError::IncorrectOperand(value))?
+        }
+        let raw_str: String = value.to_string().replace("-", "");
+        Ok(raw_str_to_buffer(raw_str, value.is_sign_negative(), digits))
+    }
+
+    /// Two's complement in the programmer mode, the sign is flipped otherwise.
+    /// Zero is left as it is.
+    pub(super) fn negate(&mut self) {
+        let value: Result<Decimal, CommandError> = (&*self).try_into();
+        let value: Decimal = match value {
+            Ok(value) if !value.is_zero() => value,
+            _ => return
+        };
+        if self.radix.is_none() {
+            self.set_negative(self.is_positive());
+            return
+        }
+        if let Ok(negated) = Self::from_dec_in(-value, self.radix, self.digits) {
+            let reset_on_clear: bool = self.reset_on_clear;
+            *self = negated;
+            self.reset_on_clear = reset_on_clear;
+        }
+    }
+
     pub(super) fn send_symbol(&mut self, symbol: char) -> bool {
         if !self.has_free_space() {
             return false;
         }
-        if self.dot_is_after.is_none() && Self::is_dot(symbol) {
+        if self.dot_is_after.is_none() && Self::is_dot(symbol) && self.radix.is_none() {
             self.dot_is_after = Some(self.buffer.chars().count() - 1);
             return true;
         }
-        if is_number(symbol) {
+        if self.is_digit(symbol) {
             if self.is_internally_empty() && !self.dot_is_here() {
                 self.buffer.clear();
             }
-            self.buffer.push(symbol);
+            self.buffer.push(symbol.to_ascii_uppercase());
+            if !self.fits_word() {
+                self.buffer.pop();
+                if self.buffer.is_empty() {
+                    self.buffer.push('0');
+                }
+                return false;
+            }
             return true;
         }
         return false;
     }
 
-    pub(super) fn _set_negative(&mut self) -> bool {
-        if self.is_negative {
+    /// Digits typed in the programmer mode do not exceed the word
+    fn fits_word(&self) -> bool {
+        let radix: Radix = match self.radix {
+            Some(radix) => radix,
+            None => return true
+        };
+        let digits: u128 = match u128::from_str_radix(&self.buffer, radix.base.radix()) {
+            Ok(digits) => digits,
+            Err(_) => return false
+        };
+        if radix.base != Base::Decimal {
+            return digits >> radix.word_size.bits() == 0
+        }
+        let value: i128 = if self.is_negative { -(digits as i128) } else { digits as i128 };
+        radix.wrap(value) == value
+    }
+
+    pub(super) fn set_negative(&mut self, is_negative: bool) -> bool {
+        if self.is_negative == is_negative {
             return false
         }
-        self.is_negative = true;
+        self.is_negative = is_negative;
         return true
     }
 
@@ -85,7 +183,23 @@ impl Operand {
     }
 
     pub fn numbers_count(&self) -> usize {
-        self.buffer.len().min(BUFFER_SIZE)
+        self.buffer.len().min(self.capacity())
+    }
+
+    /// Number of digits fitting the display
+    pub fn capacity(&self) -> usize {
+        self.radix.map_or(self.digits.get(), Radix::max_digits)
+    }
+
+    pub fn radix(&self) -> Option<Radix> {
+        self.radix
+    }
+
+    fn is_digit(&self, symbol: char) -> bool {
+        match self.radix {
+            Some(radix) => radix.base.is_digit(symbol),
+            None => is_number(symbol)
+        }
     }
 
     pub fn has_dot(&self) -> bool {
@@ -108,7 +222,7 @@ impl Operand {
     }
 
     fn has_free_space(&self) -> bool {
-        self.buffer.chars().count() < BUFFER_SIZE
+        self.buffer.chars().count() < self.capacity()
     }
 
     fn buffer_is_full(&self) -> bool {
@@ -145,13 +259,20 @@ impl Operand {
             }
             digits_not_to_count
         };
-        let extra_digits_count: usize = digits_count - BUFFER_SIZE.min(digits_count);
+        let extra_digits_count: usize = digits_count - self.capacity().min(digits_count);
         for _ in 0..extra_digits_count {
             basic_str.pop();
         }
         return basic_str;
     }
 
+    /// Value typed in the programmer mode
+    fn to_integer(&self) -> Result<i128, CommandError> {
+        let value: Decimal = self.try_into()?;
+        value.to_i128()
+            .ok_or(CommandError::IncorrectOperand(value))
+    }
+
     fn is_dot(symbol: char) -> bool {
         symbol == '.'|| symbol == ','
     }
@@ -166,6 +287,8 @@ impl Default for Operand {
             dot_is_after: None,
             is_negative: false,
             reset_on_clear: false,
+            radix: None,
+            digits: DigitCapacity::default(),
         }
     }
 }
@@ -182,7 +305,7 @@ impl ToString for Operand {
     fn to_string(&self) -> String {
         const DOT_SIZE: usize = 1;
         const MINUS_SIGN_SIZE: usize = 1;
-        let mut s = String::with_capacity(BUFFER_SIZE + DOT_SIZE + MINUS_SIGN_SIZE);
+        let mut s = String::with_capacity(self.capacity() + DOT_SIZE + MINUS_SIGN_SIZE);
         if self.is_negative {
             s.push('-');
         }
@@ -215,31 +338,7 @@ fn t_general() {
     // let number = dec!();
 }
 
-impl TryFrom<Decimal> for Operand {
-    type Error = CommandError;
-
-    fn try_from(value: Decimal) -> Result<Self, Self::Error> {
-        if value.trunc().abs() > BUFFER_MAX_NUMBER_DEC {
-            Err(CommandError::IncorrectOperand(value))?
-        }
-        let raw_str: String = value.to_string().replace("-", "");
-        Ok(raw_str_to_buffer(raw_str, value.is_sign_negative()))
-    }
-}
-
-impl TryFrom<f64> for Operand {
-    type Error = CommandError;
-
-    fn try_from(value: f64) -> Result<Self, Self::Error> {
-        if value.trunc().abs() > BUFFER_MAX_NUMBER {
-            Err(CommandError::IncorrectOperand(Decimal::from_f64(value).unwrap()))?
-        }
-        let raw_str: String = value.to_string().replace("-", ""); // allocation
-        Ok(raw_str_to_buffer(raw_str, value < 0.0))
-    }
-}
-
-fn raw_str_to_buffer(raw_str: String, is_negative: bool) -> Operand {
+fn raw_str_to_buffer(raw_str: String, is_negative: bool, digits: DigitCapacity) -> Operand {
     let total_chars_count: usize = raw_str.chars().count();
     let last_index: usize = total_chars_count.min(BUFFER_SIZE_INTERN);
     let dot_pos: Option<usize> = raw_str[0..last_index].find('.');
@@ -256,6 +355,7 @@ fn raw_str_to_buffer(raw_str: String, is_negative: bool) -> Operand {
         buffer,
         dot_is_after: dot_pos.map(|dot_pos| dot_pos - 1),
         is_negative,
+        digits,
         ..Default::default()
     }
 }
@@ -264,9 +364,7 @@ impl TryInto<Decimal> for Operand {
     type Error = CommandError;
 
     fn try_into(self) -> Result<Decimal, Self::Error> {
-        let s: String = self.to_string();
-        Ok(Decimal::from_str(&s)
-            .map_err(|_| CommandError::ParsingFailure { err: None })?)
+        (&self).try_into()
     }
 }
 
@@ -274,6 +372,14 @@ impl TryInto<Decimal> for &Operand {
     type Error = CommandError;
 
     fn try_into(self) -> Result<Decimal, Self::Error> {
+        if let Some(radix) = self.radix {
+            let digits: u128 = u128::from_str_radix(&self.buffer, radix.base.radix())
+                .map_err(|_| CommandError::ParsingFailure { err: None })?;
+            // a bit pattern typed in hexadecimal is negative if its sign bit is set
+            let value: i128 = radix.wrap(digits as i128);
+            let value: i128 = radix.wrap(if self.is_negative { -value } else { value });
+            return Ok(Decimal::from(value))
+        }
         let s: String = self.to_string();
         Ok(Decimal::from_str(&s)
             .map_err(|_| CommandError::ParsingFailure { err: None })?)
@@ -284,8 +390,25 @@ impl TryInto<f64> for Operand {
     type Error = CommandError;
 
     fn try_into(self) -> Result<f64, Self::Error> {
+        if self.radix.is_some() {
+            return Ok(self.to_integer()? as f64)
+        }
         let s: String = self.to_string();
         Ok(f64::from_str(&s)
             .map_err(|err| CommandError::ParsingFailure { err: Some(err) })?)
     }
+}
+
+impl TryInto<f32> for Operand {
+    type Error = CommandError;
+
+    /// Parses the digits at once, rounding through `f64` might round twice
+    fn try_into(self) -> Result<f32, Self::Error> {
+        if self.radix.is_some() {
+            return Ok(self.to_integer()? as f32)
+        }
+        let s: String = self.to_string();
+        f32::from_str(&s)
+            .map_err(|err| CommandError::ParsingFailure { err: Some(err) })
+    }
 }
\ No newline at end of file
@@ -1,13 +1,22 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
 
-use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal::{Decimal, MathematicalOps, prelude::ToPrimitive};
+use serde::{Serialize, Deserialize};
 
-use crate::calculator::BUFFER_SIZE;
+use super::{CommandError, maths, settings::{Settings, DivisionRounding, Radix}};
 
-use super::{CommandError, BUFFER_MAX_NUMBER_DEC};
+// Decimal places the series are accurate to, the display rounds to fewer of them.
+// Rounding hides the error of the last digits, e.g. sin 30° = 0.5
+const TRIGONOMETRY_DECIMAL_POINTS: u32 = 24;
 
+// inverse hyperbolic functions go through the logarithm, asinh(sinh 10) = 10
+const HYPERBOLIC_DECIMAL_POINTS: u32 = 20;
 
-#[derive(Debug, Clone, Copy)]
+// log10(1000) = 3 instead of 2.9999999999999999999999999999
+const LOGARITHM_DECIMAL_POINTS: u32 = 20;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Operator {
     Division,
     Multiplication,
@@ -17,64 +26,268 @@ pub enum Operator {
     NaturalLogarithm,
     Sine,
     Cosine,
+    Tangent,
+    ArcSine,
+    ArcCosine,
+    ArcTangent,
+    HyperbolicSine,
+    HyperbolicCosine,
+    HyperbolicTangent,
+    HyperbolicArcSine,
+    HyperbolicArcCosine,
+    HyperbolicArcTangent,
+    SquareRoot,
+    /// `left` is the radicand and `right` is the degree of the root
+    Root,
+    Reciprocal,
+    Square,
+    AbsoluteValue,
+    DecimalLogarithm,
+    BinaryLogarithm,
+    /// `left` is the base and `right` is the argument of the logarithm
+    Logarithm,
+    Exponential,
+    DecimalExponential,
+    Modulo,
+    IntegerDivision,
+    Factorial,
+    /// Number of ways to choose `right` of `left` items
+    Combinations,
+    /// Number of ordered arrangements of `right` of `left` items
+    Permutations,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseNot,
+    ShiftLeft,
+    /// Arithmetic for signed integers, logical for unsigned ones
+    ShiftRight,
+    RotateLeft,
+    RotateRight,
 }
 
 impl Operator {
-    pub fn apply(&self, left: Decimal, right: Option<Decimal>) -> Result<Decimal, CommandError> {
-        println!(); ////
-        println!("OPERATOR:"); ////
-        dbg!(self); ////
-        println!("CALCULATION:\n{} and {} =", left, right.unwrap_or(Decimal::ZERO)); ////
+    pub fn apply(&self, 
+                 left: Decimal, 
+                 right: Option<Decimal>, 
+                 settings: &Settings) -> Result<Decimal, CommandError> {
+        if let Some(radix) = settings.radix() {
+            let left: i128 = radix.wrap(to_integer(left)?);
+            let right: Option<i128> = match right {
+                Some(right) => Some(radix.wrap(to_integer(right)?)),
+                None => None
+            };
+            return self.apply_integer(left, right, radix, settings).map(Decimal::from)
+        }
         let result: Result<Decimal, CommandError> = match self {
-            Operator::Division => self.valid_result(
-                left.checked_div(require_operand(right)?)
-                    .ok_or(CommandError::Overflow)?),
+            Operator::Division => {
+                let right: Decimal = require_operand(right)?;
+                if right.is_zero() {
+                    Err(CommandError::DivisionByZero)?
+                }
+                self.valid_result(
+                    left.checked_div(right)
+                        .ok_or(CommandError::Overflow)?, settings)
+            },
             Operator::Multiplication => self.valid_result(
                 left.checked_mul(require_operand(right)?)
-                    .ok_or(CommandError::Overflow)?),
+                    .ok_or(CommandError::Overflow)?, settings),
             Operator::Addition => self.valid_result(
                 left.checked_add(require_operand(right)?)
-                    .ok_or(CommandError::Overflow)?),
+                    .ok_or(CommandError::Overflow)?, settings),
             Operator::Subtraction => self.valid_result(
                 left.checked_sub(require_operand(right)?)
-                    .ok_or(CommandError::Overflow)?),
+                    .ok_or(CommandError::Overflow)?, settings),
             Operator::Power => {
-                let right: Decimal = require_operand(right)?;
-
-                // last index - 1 (pre-last) - 1 (leading zero)
-                const DECIMAL_POINT_TO_ROUND_TO: u32 = (BUFFER_SIZE - 1 - 1) as u32; // default: 21
-
-                let result: Decimal = if right.scale() == 0 {
-                    left.checked_powd(right)
-                        .ok_or(CommandError::Overflow)?
-                } else {
-                    let ln_left: Decimal = left.ln();
-                    let tolerance = Decimal::ZERO;
-                    right.checked_mul(ln_left)
-                        .ok_or(CommandError::Overflow)?
-                        .checked_exp_with_tolerance(tolerance)
-                        .ok_or(CommandError::Overflow)?
-                }
-                .round_dp(DECIMAL_POINT_TO_ROUND_TO);
-
-                self.valid_result(result)
+                let exponent: Decimal = require_operand(right)?;
+                self.valid_result(power(left, exponent)?
+                    .round_dp(settings.digits.decimal_points()), settings)
             },
             Operator::NaturalLogarithm => self.valid_result(
                 left.checked_ln()
-                    .ok_or(CommandError::Overflow)?),
+                    .ok_or(CommandError::OutOfDomain(*self, left))?, settings),
             Operator::Sine => self.valid_result(
-                left.checked_sin()
-                    .ok_or(CommandError::Overflow)?),
+                maths::sin(settings.angle_unit.to_radians(left))
+                    .round_dp(decimal_points(TRIGONOMETRY_DECIMAL_POINTS, settings)), settings),
             Operator::Cosine => self.valid_result(
-                left.checked_cos()
-                    .ok_or(CommandError::Overflow)?),
+                maths::cos(settings.angle_unit.to_radians(left))
+                    .round_dp(decimal_points(TRIGONOMETRY_DECIMAL_POINTS, settings)), settings),
+            Operator::Tangent => self.valid_result(
+                maths::tan(settings.angle_unit.to_radians(left))
+                    .ok_or(CommandError::OutOfDomain(*self, left))?
+                    .round_dp(decimal_points(TRIGONOMETRY_DECIMAL_POINTS, settings)), settings),
+            Operator::ArcSine => self.valid_result(
+                settings.angle_unit.radians_to_unit(
+                    maths::asin(left)
+                        .ok_or(CommandError::OutOfDomain(*self, left))?)
+                    .round_dp(decimal_points(TRIGONOMETRY_DECIMAL_POINTS, settings)), settings),
+            Operator::ArcCosine => self.valid_result(
+                settings.angle_unit.radians_to_unit(
+                    maths::acos(left)
+                        .ok_or(CommandError::OutOfDomain(*self, left))?)
+                    .round_dp(decimal_points(TRIGONOMETRY_DECIMAL_POINTS, settings)), settings),
+            Operator::ArcTangent => self.valid_result(
+                settings.angle_unit.radians_to_unit(maths::atan(left))
+                    .round_dp(decimal_points(TRIGONOMETRY_DECIMAL_POINTS, settings)), settings),
+            Operator::HyperbolicSine => self.valid_result(
+                maths::sinh(left)
+                    .ok_or(CommandError::Overflow)?
+                    .round_dp(decimal_points(HYPERBOLIC_DECIMAL_POINTS, settings)), settings),
+            Operator::HyperbolicCosine => self.valid_result(
+                maths::cosh(left)
+                    .ok_or(CommandError::Overflow)?
+                    .round_dp(decimal_points(HYPERBOLIC_DECIMAL_POINTS, settings)), settings),
+            Operator::HyperbolicTangent => self.valid_result(
+                maths::tanh(left)
+                    .round_dp(decimal_points(HYPERBOLIC_DECIMAL_POINTS, settings)), settings),
+            Operator::HyperbolicArcSine => self.valid_result(
+                maths::asinh(left)
+                    .ok_or(CommandError::Overflow)?
+                    .round_dp(decimal_points(HYPERBOLIC_DECIMAL_POINTS, settings)), settings),
+            Operator::HyperbolicArcCosine => self.valid_result(
+                maths::acosh(left)
+                    .ok_or(CommandError::OutOfDomain(*self, left))?
+                    .round_dp(decimal_points(HYPERBOLIC_DECIMAL_POINTS, settings)), settings),
+            Operator::HyperbolicArcTangent => self.valid_result(
+                maths::atanh(left)
+                    .ok_or(CommandError::OutOfDomain(*self, left))?
+                    .round_dp(decimal_points(HYPERBOLIC_DECIMAL_POINTS, settings)), settings),
+            Operator::SquareRoot => self.valid_result(
+                left.sqrt()
+                    .ok_or(CommandError::ComplexResult(*self, left))?, settings),
+            Operator::Root => {
+                let degree: Decimal = require_operand(right)?;
+                self.valid_result(root(*self, left, degree)?
+                    .round_dp(settings.digits.decimal_points()), settings)
+            },
+            Operator::Reciprocal => {
+                if left.is_zero() {
+                    Err(CommandError::DivisionByZero)?
+                }
+                self.valid_result(
+                    Decimal::ONE.checked_div(left)
+                        .ok_or(CommandError::Overflow)?, settings)
+            },
+            Operator::Square => self.valid_result(
+                left.checked_mul(left)
+                    .ok_or(CommandError::Overflow)?, settings),
+            Operator::AbsoluteValue => Ok(left.abs()),
+            Operator::DecimalLogarithm => self.valid_result(
+                left.checked_log10()
+                    .ok_or(CommandError::OutOfDomain(*self, left))?
+                    .round_dp(decimal_points(LOGARITHM_DECIMAL_POINTS, settings)), settings),
+            Operator::BinaryLogarithm => self.valid_result(
+                logarithm(*self, Decimal::TWO, left)?
+                    .round_dp(decimal_points(LOGARITHM_DECIMAL_POINTS, settings)), settings),
+            Operator::Logarithm => self.valid_result(
+                logarithm(*self, left, require_operand(right)?)?
+                    .round_dp(decimal_points(LOGARITHM_DECIMAL_POINTS, settings)), settings),
+            Operator::Exponential => self.valid_result(
+                maths::exp(left)
+                    .ok_or(CommandError::Overflow)?
+                    .round_dp(settings.digits.decimal_points()), settings),
+            Operator::DecimalExponential => self.valid_result(
+                power(Decimal::TEN, left)?
+                    .round_dp(settings.digits.decimal_points()), settings),
+            Operator::Factorial => self.valid_result(
+                factorial(*self, left)?
+                    .round_dp(settings.digits.decimal_points()), settings),
+            Operator::Combinations => {
+                let (n, r): (Decimal, Decimal) = combinatorics_operands(*self, left, require_operand(right)?)?;
+                self.valid_result(combinations(n, r, settings.digits.max_number())?, settings)
+            },
+            Operator::Permutations => {
+                let (n, r): (Decimal, Decimal) = combinatorics_operands(*self, left, require_operand(right)?)?;
+                self.valid_result(permutations(n, r, settings.digits.max_number())?, settings)
+            },
+            Operator::BitwiseAnd
+            | Operator::BitwiseOr
+            | Operator::BitwiseXor
+            | Operator::BitwiseNot
+            | Operator::ShiftLeft
+            | Operator::ShiftRight
+            | Operator::RotateLeft
+            | Operator::RotateRight => Err(CommandError::IncorrectOperation(
+                format!("{} is available in the programmer mode only", self)))?,
+            Operator::Modulo | Operator::IntegerDivision => {
+                let right: Decimal = require_operand(right)?;
+                if right.is_zero() {
+                    Err(CommandError::DivisionByZero)?
+                }
+                let rounding: DivisionRounding = settings.division_rounding;
+                self.valid_result(match self {
+                    Operator::Modulo => rounding.remainder(left, right),
+                    _ => rounding.quotient(left, right),
+                }, settings)
+            },
         };
         result.map(|result| result.normalize())
     }
 
-    fn valid_result(&self, result: Decimal) -> Result<Decimal, CommandError> {
-        dbg!(result); ////
-        if result.trunc().abs() > BUFFER_MAX_NUMBER_DEC {
+    /// Arithmetic of the programmer mode, results wrap around the word
+    fn apply_integer(&self,
+                     left: i128,
+                     right: Option<i128>,
+                     radix: Radix,
+                     settings: &Settings) -> Result<i128, CommandError> {
+        let bits: u32 = radix.word_size.bits();
+        let result: i128 = match self {
+            Operator::Addition => left.wrapping_add(require_operand(right)?),
+            Operator::Subtraction => left.wrapping_sub(require_operand(right)?),
+            Operator::Multiplication => left.wrapping_mul(require_operand(right)?),
+            Operator::Division | Operator::Modulo | Operator::IntegerDivision => {
+                let right: i128 = require_operand(right)?;
+                if right == 0 {
+                    Err(CommandError::DivisionByZero)?
+                }
+                let rounding: DivisionRounding = settings.division_rounding;
+                let (left_dec, right_dec): (Decimal, Decimal) = (Decimal::from(left), Decimal::from(right));
+                match self {
+                    // truncated as in most programming languages
+                    Operator::Division => left.wrapping_div(right),
+                    Operator::Modulo => rounding.remainder(left_dec, right_dec).to_i128()
+                        .ok_or(CommandError::Overflow)?,
+                    _ => rounding.quotient(left_dec, right_dec).to_i128()
+                        .ok_or(CommandError::Overflow)?,
+                }
+            },
+            Operator::Power => {
+                let exponent: i128 = require_operand(right)?;
+                let exponent: u32 = u32::try_from(exponent)
+                    .map_err(|_| CommandError::OutOfDomain(*self, Decimal::from(exponent)))?;
+                left.wrapping_pow(exponent)
+            },
+            Operator::Square => left.wrapping_mul(left),
+            Operator::AbsoluteValue => left.wrapping_abs(),
+            Operator::BitwiseAnd => left & require_operand(right)?,
+            Operator::BitwiseOr => left | require_operand(right)?,
+            Operator::BitwiseXor => left ^ require_operand(right)?,
+            Operator::BitwiseNot => !left,
+            Operator::ShiftLeft | Operator::ShiftRight => {
+                let shift: u32 = shift_amount(*self, require_operand(right)?)?.min(bits);
+                match self {
+                    Operator::ShiftLeft => left << shift,
+                    // sign bit is kept as far as the signed integers are negative
+                    _ => left >> shift,
+                }
+            },
+            Operator::RotateLeft | Operator::RotateRight => {
+                let shift: u32 = shift_amount(*self, require_operand(right)?)? % bits;
+                let shift: u32 = match self {
+                    Operator::RotateLeft => shift,
+                    _ => (bits - shift) % bits,
+                };
+                let pattern: u128 = radix.bit_pattern(left).into();
+                (pattern << shift | pattern >> (bits - shift)) as i128
+            },
+            _ => Err(CommandError::IncorrectOperation(
+                format!("{} is not available in the programmer mode", self)))?
+        };
+        Ok(radix.wrap(result))
+    }
+
+    fn valid_result(&self, result: Decimal, settings: &Settings) -> Result<Decimal, CommandError> {
+        if result.trunc().abs() > settings.digits.max_number() {
             Err(CommandError::OutOfBufferRange(result))?
         }
         Ok(result)
@@ -90,11 +303,278 @@ impl Operator {
             Operator::NaturalLogarithm => true,
             Operator::Sine => true,
             Operator::Cosine => true,
+            Operator::Tangent => true,
+            Operator::ArcSine => true,
+            Operator::ArcCosine => true,
+            Operator::ArcTangent => true,
+            Operator::HyperbolicSine => true,
+            Operator::HyperbolicCosine => true,
+            Operator::HyperbolicTangent => true,
+            Operator::HyperbolicArcSine => true,
+            Operator::HyperbolicArcCosine => true,
+            Operator::HyperbolicArcTangent => true,
+            Operator::SquareRoot => true,
+            Operator::Root => false,
+            Operator::Reciprocal => true,
+            Operator::Square => true,
+            Operator::AbsoluteValue => true,
+            Operator::DecimalLogarithm => true,
+            Operator::BinaryLogarithm => true,
+            Operator::Logarithm => false,
+            Operator::Exponential => true,
+            Operator::DecimalExponential => true,
+            Operator::Modulo => false,
+            Operator::IntegerDivision => false,
+            Operator::Factorial => true,
+            Operator::Combinations => false,
+            Operator::Permutations => false,
+            Operator::BitwiseAnd => false,
+            Operator::BitwiseOr => false,
+            Operator::BitwiseXor => false,
+            Operator::BitwiseNot => true,
+            Operator::ShiftLeft => false,
+            Operator::ShiftRight => false,
+            Operator::RotateLeft => false,
+            Operator::RotateRight => false,
+        }
+    }
+
+    /// Binary operators of higher precedence are applied first in the algebraic mode
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Operator::Addition | Operator::Subtraction => 1,
+            Operator::Multiplication
+            | Operator::Division
+            | Operator::Modulo
+            | Operator::IntegerDivision => 2,
+            _ => 3,
+        }
+    }
+
+    /// 2 ^ 3 ^ 2 = 2 ^ 9
+    pub fn is_right_associative(&self) -> bool {
+        matches!(self, Operator::Power)
+    }
+
+    /// Hyperbolic counterpart of a trigonometric operator, the rest are kept as is
+    pub fn hyperbolic(self) -> Self {
+        match self {
+            Operator::Sine => Operator::HyperbolicSine,
+            Operator::Cosine => Operator::HyperbolicCosine,
+            Operator::Tangent => Operator::HyperbolicTangent,
+            Operator::ArcSine => Operator::HyperbolicArcSine,
+            Operator::ArcCosine => Operator::HyperbolicArcCosine,
+            Operator::ArcTangent => Operator::HyperbolicArcTangent,
+            operator => operator,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Operator::Division => "÷",
+            Operator::Multiplication => "×",
+            Operator::Addition => "+",
+            Operator::Subtraction => "-",
+            Operator::Power => "^",
+            Operator::NaturalLogarithm => "ln",
+            Operator::Sine => "sin",
+            Operator::Cosine => "cos",
+            Operator::Tangent => "tan",
+            Operator::ArcSine => "asin",
+            Operator::ArcCosine => "acos",
+            Operator::ArcTangent => "atan",
+            Operator::HyperbolicSine => "sinh",
+            Operator::HyperbolicCosine => "cosh",
+            Operator::HyperbolicTangent => "tanh",
+            Operator::HyperbolicArcSine => "asinh",
+            Operator::HyperbolicArcCosine => "acosh",
+            Operator::HyperbolicArcTangent => "atanh",
+            Operator::SquareRoot => "√",
+            Operator::Root => "root",
+            Operator::Reciprocal => "1/",
+            Operator::Square => "sqr",
+            Operator::AbsoluteValue => "abs",
+            Operator::DecimalLogarithm => "log",
+            Operator::BinaryLogarithm => "log₂",
+            Operator::Logarithm => "logₐ",
+            Operator::Exponential => "exp",
+            Operator::DecimalExponential => "10^",
+            Operator::Modulo => "mod",
+            Operator::IntegerDivision => "div",
+            Operator::Factorial => "fact",
+            Operator::Combinations => "nCr",
+            Operator::Permutations => "nPr",
+            Operator::BitwiseAnd => "AND",
+            Operator::BitwiseOr => "OR",
+            Operator::BitwiseXor => "XOR",
+            Operator::BitwiseNot => "NOT",
+            Operator::ShiftLeft => "<<",
+            Operator::ShiftRight => ">>",
+            Operator::RotateLeft => "ROL",
+            Operator::RotateRight => "ROR",
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Exact for non-negative integers, Γ(x + 1) for the rest.
+/// The results overflowing `Decimal` are reported as its maximum, 27! is the largest one fitting.
+fn factorial(operator: Operator, x: Decimal) -> Result<Decimal, CommandError> {
+    let out_of_range = || CommandError::OutOfBufferRange(Decimal::MAX);
+    if !x.fract().is_zero() {
+        return maths::gamma(x + Decimal::ONE)
+            .ok_or_else(out_of_range)
+    }
+    if x.is_sign_negative() {
+        Err(CommandError::OutOfDomain(operator, x))?
+    }
+    let mut result: Decimal = Decimal::ONE;
+    let mut factor: Decimal = Decimal::TWO;
+    while factor <= x {
+        result = result.checked_mul(factor)
+            .ok_or_else(out_of_range)?;
+        factor += Decimal::ONE;
+    }
+    Ok(result)
+}
+
+fn combinatorics_operands(operator: Operator,
+                          n: Decimal,
+                          r: Decimal) -> Result<(Decimal, Decimal), CommandError> {
+    for operand in [n, r] {
+        if operand.is_sign_negative() || !operand.fract().is_zero() {
+            Err(CommandError::OutOfDomain(operator, operand))?
+        }
+    }
+    Ok((n, r))
+}
+
+/// n! / (r! (n - r)!) multiplied and divided in turn so that every partial result is an integer
+fn combinations(n: Decimal, r: Decimal, max: Decimal) -> Result<Decimal, CommandError> {
+    if r > n {
+        return Ok(Decimal::ZERO)
+    }
+    let r: Decimal = r.min(n - r);
+    let mut result: Decimal = Decimal::ONE;
+    let mut i: Decimal = Decimal::ONE;
+    while i <= r {
+        // C(n - r + i, i) grows with i
+        result = result.checked_mul(n - r + i)
+            .ok_or(CommandError::Overflow)?
+            / i;
+        if result > max {
+            Err(CommandError::OutOfBufferRange(result))?
+        }
+        i += Decimal::ONE;
+    }
+    Ok(result)
+}
+
+/// n! / (n - r)! as the product n (n - 1) ... (n - r + 1)
+fn permutations(n: Decimal, r: Decimal, max: Decimal) -> Result<Decimal, CommandError> {
+    if r > n {
+        return Ok(Decimal::ZERO)
+    }
+    let mut result: Decimal = Decimal::ONE;
+    let mut factor: Decimal = n - r + Decimal::ONE;
+    while factor <= n {
+        result = result.checked_mul(factor)
+            .ok_or(CommandError::Overflow)?;
+        if result > max {
+            Err(CommandError::OutOfBufferRange(result))?
+        }
+        factor += Decimal::ONE;
+    }
+    Ok(result)
+}
+
+fn logarithm(operator: Operator, base: Decimal, argument: Decimal) -> Result<Decimal, CommandError> {
+    if base <= Decimal::ZERO || base == Decimal::ONE {
+        Err(CommandError::OutOfDomain(operator, base))?
+    }
+    let ln_argument: Decimal = argument.checked_ln()
+        .ok_or(CommandError::OutOfDomain(operator, argument))?;
+    ln_argument.checked_div(base.ln())
+        .ok_or(CommandError::Overflow)
+}
+
+fn shift_amount(operator: Operator, amount: i128) -> Result<u32, CommandError> {
+    u32::try_from(amount)
+        .map_err(|_| CommandError::OutOfDomain(operator, Decimal::from(amount)))
+}
+
+fn to_integer(value: Decimal) -> Result<i128, CommandError> {
+    value.trunc()
+        .to_i128()
+        .ok_or(CommandError::Overflow)
+}
+
+/// Negative base is allowed for exponents which are fractions with odd denominator
+fn power(base: Decimal, exponent: Decimal) -> Result<Decimal, CommandError> {
+    if base.is_zero() {
+        return match exponent {
+            exponent if exponent.is_sign_negative() => Err(CommandError::DivisionByZero),
+            exponent if exponent.is_zero() => Ok(Decimal::ONE),
+            _ => Ok(Decimal::ZERO),
         }
     }
+    if exponent.fract().is_zero() {
+        return base.checked_powd(exponent)
+            .ok_or(CommandError::Overflow)
+    }
+    if base.is_sign_positive() {
+        let exponent: Decimal = exponent.checked_mul(base.ln())
+            .ok_or(CommandError::Overflow)?;
+        return maths::exp(exponent)
+            .ok_or(CommandError::Overflow)
+    }
+    let (numerator, denominator): (i64, i64) = maths::rational(exponent)
+        .filter(|(_, denominator)| denominator % 2 == 1)
+        .ok_or(CommandError::ComplexResult(Operator::Power, base))?;
+    let exponent: Decimal = base.abs().ln()
+        .checked_mul(Decimal::from(numerator))
+        .and_then(|exponent| exponent.checked_div(Decimal::from(denominator)))
+        .ok_or(CommandError::Overflow)?;
+    let magnitude: Decimal = maths::exp(exponent)
+        .ok_or(CommandError::Overflow)?;
+    Ok(if numerator % 2 == 0 { magnitude } else { -magnitude })
+}
+
+/// `degree`-th root of `radicand`, odd roots of negative numbers are real
+fn root(operator: Operator, radicand: Decimal, degree: Decimal) -> Result<Decimal, CommandError> {
+    if degree.is_zero() {
+        Err(CommandError::DivisionByZero)?
+    }
+    if radicand.is_zero() {
+        if degree.is_sign_negative() {
+            Err(CommandError::DivisionByZero)?
+        }
+        return Ok(Decimal::ZERO)
+    }
+    let is_odd_degree: bool = degree.fract().is_zero()
+        && !(degree % Decimal::TWO).is_zero();
+    if radicand.is_sign_negative() && !is_odd_degree {
+        Err(CommandError::ComplexResult(operator, radicand))?
+    }
+    let exponent: Decimal = radicand.abs().ln()
+        .checked_div(degree)
+        .ok_or(CommandError::Overflow)?;
+    let result: Decimal = maths::exp(exponent)
+        .ok_or(CommandError::Overflow)?;
+    Ok(if radicand.is_sign_negative() { -result } else { result })
+}
+
+/// Decimal places of the display, unless the series is accurate to fewer of them
+fn decimal_points(series_decimal_points: u32, settings: &Settings) -> u32 {
+    settings.digits.decimal_points().min(series_decimal_points)
 }
 
-fn require_operand(operand: Option<Decimal>) -> Result<Decimal, CommandError> {
+fn require_operand<T>(operand: Option<T>) -> Result<T, CommandError> {
     operand.ok_or(CommandError::OperandIsMissing)
 }
 
@@ -108,6 +588,12 @@ impl TryFrom<char> for Operator {
             '+' => Ok(Operator::Addition),
             '-' => Ok(Operator::Subtraction),
             '^' => Ok(Operator::Power),
+            'm' => Ok(Operator::Modulo),
+            '\\' => Ok(Operator::IntegerDivision),
+            '&' => Ok(Operator::BitwiseAnd),
+            '|' => Ok(Operator::BitwiseOr),
+            '<' => Ok(Operator::ShiftLeft),
+            '>' => Ok(Operator::ShiftRight),
             _ => Err(CommandError::IncorrectOperation("Incorrect operation symbol".into()))?
         }
     }
@@ -0,0 +1,341 @@
+use std::fmt::{self, Display};
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Serialize, Deserialize};
+
+/// Modes of the calculator switched at runtime
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Repeated '=' after multiplication or division keeps the left operand 
+    /// as the constant instead of the right one
+    pub k_constant: bool,
+    /// Multiplication and division are applied before addition and subtraction,
+    /// power before both of them
+    pub algebraic: bool,
+    /// Unit of the operands of trigonometric operators
+    pub angle_unit: AngleUnit,
+    /// Sign of `mod` and `div` results for negative operands
+    pub division_rounding: DivisionRounding,
+    /// Integer arithmetic in the chosen base
+    pub programmer: bool,
+    /// Numeral system of the programmer mode
+    pub base: Base,
+    /// Integers of the programmer mode wrap around on overflow of the word
+    pub word_size: WordSize,
+    /// Integers of the programmer mode are non-negative,
+    /// two's complement is used for negative ones otherwise
+    pub unsigned: bool,
+    /// Digits of the display outside of the programmer mode
+    pub digits: DigitCapacity,
+}
+
+impl Settings {
+    /// Integer format of the operands, none outside of the programmer mode
+    pub fn radix(&self) -> Option<Radix> {
+        self.programmer.then_some(Radix {
+            base: self.base,
+            word_size: self.word_size,
+            unsigned: self.unsigned,
+        })
+    }
+
+    /// Digits of the operands in the current mode
+    pub fn capacity(&self) -> usize {
+        self.radix().map_or(self.digits.get(), Radix::max_digits)
+    }
+
+    /// Takes the settings the operands are written in from `other`, keeps the rest
+    pub(super) fn with_format_of(self, other: Settings) -> Self {
+        Self {
+            programmer: other.programmer,
+            base: other.base,
+            word_size: other.word_size,
+            unsigned: other.unsigned,
+            digits: other.digits,
+            ..self
+        }
+    }
+}
+
+/// Integer format of the programmer mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Radix {
+    pub base: Base,
+    pub word_size: WordSize,
+    pub unsigned: bool,
+}
+
+impl Radix {
+    /// Digits of the longest number of the word
+    pub fn max_digits(self) -> usize {
+        let bits: u32 = self.word_size.bits();
+        match self.base {
+            // 2^bits has as many digits as 2^bits - 1, one more for the sign of -2^(bits - 1)
+            Base::Decimal => (1u128 << bits).to_string().len(),
+            Base::Hexadecimal => (bits / 4) as usize,
+            Base::Octal => bits.div_ceil(3) as usize,
+            Base::Binary => bits as usize,
+        }
+    }
+
+    /// Brings an integer into the range of the word, two's complement for the signed ones
+    pub fn wrap(self, value: i128) -> i128 {
+        let bits: u32 = self.word_size.bits();
+        let pattern: i128 = self.bit_pattern(value) as i128;
+        if !self.unsigned && pattern >> (bits - 1) == 1 {
+            pattern - (1 << bits)
+        } else {
+            pattern
+        }
+    }
+
+    /// Bits of the integer as they are stored in the word
+    pub fn bit_pattern(self, value: i128) -> u64 {
+        let mask: u128 = (1 << self.word_size.bits()) - 1;
+        (value as u128 & mask) as u64
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WordSize {
+    Byte,
+    Word,
+    DoubleWord,
+    #[default]
+    QuadWord,
+}
+
+impl WordSize {
+    pub fn bits(self) -> u32 {
+        match self {
+            WordSize::Byte => 8,
+            WordSize::Word => 16,
+            WordSize::DoubleWord => 32,
+            WordSize::QuadWord => 64,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            WordSize::Byte => WordSize::Word,
+            WordSize::Word => WordSize::DoubleWord,
+            WordSize::DoubleWord => WordSize::QuadWord,
+            WordSize::QuadWord => WordSize::Byte,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WordSize::Byte => "BYTE",
+            WordSize::Word => "WORD",
+            WordSize::DoubleWord => "DWORD",
+            WordSize::QuadWord => "QWORD",
+        }
+    }
+}
+
+impl Display for WordSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Number of digits the display holds, the largest operand consists of as many nines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "usize")]
+pub struct DigitCapacity(usize);
+
+impl DigitCapacity {
+    pub const ALL: [usize; 7] = [8, 10, 12, 16, 20, 24, 28];
+    /// Significant digits of `Decimal`
+    pub const MAX: usize = 28;
+
+    pub fn new(digits: usize) -> Option<Self> {
+        Self::ALL.contains(&digits).then_some(Self(digits))
+    }
+
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    pub fn max_number(self) -> Decimal {
+        Decimal::from_i128_with_scale(10i128.pow(self.0 as u32) - 1, 0)
+    }
+
+    /// Decimal places of the rounded results:
+    /// last index - 1 (pre-last) - 1 (leading zero)
+    pub fn decimal_points(self) -> u32 {
+        (self.0 - 1 - 1) as u32
+    }
+
+    pub fn next(self) -> Self {
+        let next_digits: usize = Self::ALL.iter()
+            .copied()
+            .find(|&digits| digits > self.0)
+            .unwrap_or(Self::ALL[0]);
+        Self(next_digits)
+    }
+}
+
+impl TryFrom<usize> for DigitCapacity {
+    type Error = String;
+
+    fn try_from(digits: usize) -> Result<Self, Self::Error> {
+        Self::new(digits).ok_or(format!("{} digits are not supported", digits))
+    }
+}
+
+impl Default for DigitCapacity {
+    fn default() -> Self {
+        Self(16)
+    }
+}
+
+impl Display for DigitCapacity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} DIG", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AngleUnit {
+    Degrees,
+    #[default]
+    Radians,
+    Grads,
+}
+
+impl AngleUnit {
+    /// Order of the DRG key
+    pub fn next(self) -> Self {
+        match self {
+            AngleUnit::Degrees => AngleUnit::Radians,
+            AngleUnit::Radians => AngleUnit::Grads,
+            AngleUnit::Grads => AngleUnit::Degrees,
+        }
+    }
+
+    /// Full turns are dropped before conversion to keep the precision of huge angles
+    pub fn to_radians(self, angle: Decimal) -> Decimal {
+        match self {
+            AngleUnit::Degrees => (angle % dec!(360)) * Decimal::PI / dec!(180),
+            AngleUnit::Radians => angle,
+            AngleUnit::Grads => (angle % dec!(400)) * Decimal::PI / dec!(200),
+        }
+    }
+
+    pub fn radians_to_unit(self, angle: Decimal) -> Decimal {
+        match self {
+            AngleUnit::Degrees => angle * dec!(180) / Decimal::PI,
+            AngleUnit::Radians => angle,
+            AngleUnit::Grads => angle * dec!(200) / Decimal::PI,
+        }
+    }
+}
+
+impl Display for AngleUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name: &str = match self {
+            AngleUnit::Degrees => "DEG",
+            AngleUnit::Radians => "RAD",
+            AngleUnit::Grads => "GRAD",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Rounding of the quotient of `div`, the remainder of `mod` follows it
+/// so that `left = right * quotient + remainder`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DivisionRounding {
+    /// Towards negative infinity, the remainder has the sign of the divisor: -7 mod 2 = 1
+    #[default]
+    Floored,
+    /// Towards zero, the remainder has the sign of the dividend: -7 mod 2 = -1
+    Truncated,
+}
+
+impl DivisionRounding {
+    pub fn toggle(self) -> Self {
+        match self {
+            DivisionRounding::Floored => DivisionRounding::Truncated,
+            DivisionRounding::Truncated => DivisionRounding::Floored,
+        }
+    }
+
+    /// Divisor must not be zero
+    pub fn remainder(self, dividend: Decimal, divisor: Decimal) -> Decimal {
+        // `%` of Decimal is truncated
+        let remainder: Decimal = dividend % divisor;
+        match self {
+            DivisionRounding::Floored if !remainder.is_zero()
+                    && remainder.is_sign_negative() != divisor.is_sign_negative() => remainder + divisor,
+            _ => remainder,
+        }
+    }
+
+    /// Divisor must not be zero
+    pub fn quotient(self, dividend: Decimal, divisor: Decimal) -> Decimal {
+        ((dividend - self.remainder(dividend, divisor)) / divisor).round()
+    }
+}
+
+impl Display for DivisionRounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name: &str = match self {
+            DivisionRounding::Floored => "FLOOR",
+            DivisionRounding::Truncated => "TRUNC",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Base {
+    #[default]
+    Decimal,
+    Hexadecimal,
+    Octal,
+    Binary,
+}
+
+impl Base {
+    pub const ALL: [Base; 4] = [Base::Decimal, Base::Hexadecimal, Base::Octal, Base::Binary];
+
+    pub fn radix(self) -> u32 {
+        match self {
+            Base::Decimal => 10,
+            Base::Hexadecimal => 16,
+            Base::Octal => 8,
+            Base::Binary => 2,
+        }
+    }
+
+    pub fn is_digit(self, symbol: char) -> bool {
+        symbol.is_digit(self.radix())
+    }
+
+    /// Digits of the number in this base, letters are uppercase
+    pub fn format(self, number: u128) -> String {
+        match self {
+            Base::Decimal => number.to_string(),
+            Base::Hexadecimal => format!("{:X}", number),
+            Base::Octal => format!("{:o}", number),
+            Base::Binary => format!("{:b}", number),
+        }
+    }
+}
+
+impl Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name: &str = match self {
+            Base::Decimal => "DEC",
+            Base::Hexadecimal => "HEX",
+            Base::Octal => "OCT",
+            Base::Binary => "BIN",
+        };
+        f.write_str(name)
+    }
+}
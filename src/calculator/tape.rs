@@ -0,0 +1,117 @@
+use std::{
+    fmt::{self, Display}, 
+    time::Duration
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+// std::time::SystemTime::now() panics in the browser
+#[cfg(target_arch = "wasm32")]
+use wasm_timer::{SystemTime, UNIX_EPOCH};
+
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+
+use super::operator::Operator;
+
+/// Completed calculation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapeEntry {
+    pub left: Decimal,
+    pub operator: Operator,
+    /// Is absent for unary operators
+    pub right: Option<Decimal>,
+    /// Right operand was taken as percentage of the left one
+    pub is_percentage: bool,
+    pub result: Decimal,
+    /// Time elapsed since UNIX epoch
+    pub timestamp: Duration,
+}
+
+impl Display for TapeEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.right {
+            Some(right) if self.is_percentage 
+                => write!(f, "{} {} {}% = {}", self.left, self.operator, right, self.result),
+            Some(right) 
+                => write!(f, "{} {} {} = {}", self.left, self.operator, right, self.result),
+            None 
+                => write!(f, "{}({}) = {}", self.operator, self.left, self.result),
+        }
+    }
+}
+
+/// Entries kept on the tape, the oldest ones are dropped beyond it
+pub const TAPE_DEPTH: usize = 256;
+
+/// Audit trail of everything the calculator has computed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Tape {
+    entries: Vec<TapeEntry>,
+    /// Entries of the action in progress, they reach the tape if it succeeds
+    #[serde(skip)]
+    pending: Vec<TapeEntry>,
+}
+
+impl Tape {
+    pub(super) fn record(&mut self, 
+                         left: Decimal, 
+                         operator: Operator, 
+                         right: Option<Decimal>, 
+                         result: Decimal) {
+        self.push(left, operator, right, false, result);
+    }
+
+    pub(super) fn record_percentage(&mut self, 
+                                    left: Decimal, 
+                                    operator: Operator, 
+                                    percent: Decimal, 
+                                    result: Decimal) {
+        self.push(left, operator, Some(percent), true, result);
+    }
+
+    fn push(&mut self, 
+            left: Decimal, 
+            operator: Operator, 
+            right: Option<Decimal>, 
+            is_percentage: bool, 
+            result: Decimal) {
+        self.pending.push(TapeEntry { 
+            left, 
+            operator, 
+            right, 
+            is_percentage, 
+            result, 
+            timestamp: now() 
+        });
+    }
+
+    /// Appends the entries of the succeeded action. Returns false if it has calculated nothing.
+    pub(super) fn commit(&mut self) -> bool {
+        if self.pending.is_empty() {
+            return false
+        }
+        self.entries.append(&mut self.pending);
+        let excess: usize = self.entries.len().saturating_sub(TAPE_DEPTH);
+        self.entries.drain(..excess);
+        true
+    }
+
+    /// Drops the entries of the failed action
+    pub(super) fn discard(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn entries(&self) -> &[TapeEntry] {
+        &self.entries
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
@@ -1,4 +1,8 @@
-use crate::calculator::Calculator;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::calculator::{Calculator, error::CommandError, operator::Operator, settings::{Base, Settings}, float::{FloatInspection, Precision}, tape::TAPE_DEPTH};
 
 #[test]
 fn test_calc() {
@@ -35,4 +39,881 @@ fn calculator() -> Result<(), Box<dyn std::error::Error>> {
 fn is_normal() {
     let a: f64 = 0.0;
     dbg!(a.is_finite());
-}
\ No newline at end of file
+}
+
+#[test]
+fn undo_redo() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('1')?
+        .symbol_in('2')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "15");
+    assert!(calc.undo());
+    assert_eq!(calc.current_operand_to_str(), "3");
+    assert!(calc.undo());
+    assert!(calc.undo());
+    assert_eq!(calc.current_operand_to_str(), "12");
+    assert!(calc.redo());
+    assert!(calc.redo());
+    assert!(calc.redo());
+    assert_eq!(calc.current_operand_to_str(), "15");
+    assert!(!calc.redo());
+
+    calc.symbol_in('1')?
+        .symbol_in('2')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .erase_all();
+    assert_eq!(calc.current_operand_to_str(), "0");
+    assert!(calc.undo());
+    assert_eq!(calc.current_operand_to_str(), "3");
+    calc.symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "15");
+
+    calc.erase_all()
+        .symbol_in('5')?
+        .symbol_in('/')?
+        .symbol_in('0')?;
+    assert!(calc.symbol_in('=').is_err());
+    // as the GUI and the CLI clear the error
+    calc.erase_all();
+    assert!(calc.undo());
+    assert_eq!(calc.current_operand_to_str(), "0");
+    calc.symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "2.5");
+    Ok(())
+}
+
+#[test]
+fn undo_skips_failures_and_no_ops() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('=')?
+        .erase();
+    assert!(!calc.undo());
+    calc.symbol_in('4')?;
+    assert!(calc.symbol_in(')').is_err());
+    assert_eq!(calc.current_operand_to_str(), "4");
+    assert!(calc.undo());
+    assert_eq!(calc.current_operand_to_str(), "0");
+    assert!(!calc.undo());
+    Ok(())
+}
+
+#[test]
+fn undo_across_mode_change() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('2')?
+        .symbol_in('5')?
+        .symbol_in('5')?;
+    calc.toggle_programmer()?;
+    calc.set_base(Base::Hexadecimal)?;
+    assert_eq!(calc.current_operand_to_str(), "FF");
+    assert!(calc.undo());
+    assert_eq!(calc.settings().base, Base::Decimal);
+    assert!(calc.undo());
+    assert!(!calc.settings().programmer);
+    assert_eq!(calc.current_operand_to_str(), "255");
+    assert!(calc.undo());
+    assert_eq!(calc.current_operand_to_str(), "25");
+    assert!(calc.redo());
+    assert!(calc.redo());
+    assert!(calc.redo());
+    assert_eq!(calc.current_operand_to_str(), "FF");
+    calc.symbol_in('A')?;
+    assert_eq!(calc.current_operand_to_str(), "A");
+    Ok(())
+}
+
+#[test]
+fn undo_is_bounded() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.set_history_depth(2);
+    calc.symbol_in('1')?
+        .symbol_in('2')?
+        .symbol_in('3')?;
+    assert!(calc.undo());
+    assert!(calc.undo());
+    assert!(!calc.undo());
+    assert_eq!(calc.current_operand_to_str(), "1");
+    Ok(())
+}
+
+#[test]
+fn tape() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('2')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .symbol_in('*')?
+        .symbol_in('4')?
+        .symbol_in('=')?
+        .operator_in(Operator::NaturalLogarithm)?;
+    let entries = calc.tape().entries();
+    assert_eq!(entries.len(), 3);
+    assert_eq!((entries[0].left, entries[0].right, entries[0].result), 
+               (dec!(2), Some(dec!(3)), dec!(5)));
+    assert_eq!((entries[1].left, entries[1].right, entries[1].result), 
+               (dec!(5), Some(dec!(4)), dec!(20)));
+    assert_eq!(entries[2].to_string(), format!("ln(20) = {}", entries[2].result));
+
+    // 0 ^ 2 is calculated before 5 / 0 fails
+    calc.clear_tape();
+    calc.toggle_algebraic();
+    calc.symbol_in('5')?
+        .symbol_in('/')?
+        .symbol_in('0')?
+        .symbol_in('^')?
+        .symbol_in('2')?;
+    assert!(calc.symbol_in('=').is_err());
+    assert!(calc.tape().entries().is_empty());
+    Ok(())
+}
+
+#[test]
+fn tape_is_bounded() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('1')?
+        .symbol_in('+')?
+        .symbol_in('1')?;
+    for _ in 0..TAPE_DEPTH + 1 {
+        calc.symbol_in('=')?;
+    }
+    let entries = calc.tape().entries();
+    assert_eq!(entries.len(), TAPE_DEPTH);
+    assert_eq!(entries[0].left, dec!(2));
+    Ok(())
+}
+
+#[test]
+fn revision_grows_with_changes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('=')?;
+    assert!(!calc.undo());
+    assert_eq!(calc.revision(), 0);
+    calc.symbol_in('5')?;
+    assert_eq!(calc.revision(), 1);
+    calc.toggle_algebraic();
+    assert_eq!(calc.revision(), 2);
+    assert!(calc.symbol_in(')').is_err());
+    assert_eq!(calc.revision(), 2);
+    Ok(())
+}
+
+#[test]
+fn session_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('5')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .memory_add()?;
+    let session: String = serde_json::to_string(&calc)?;
+    let mut calc: Calculator = serde_json::from_str(&session)?;
+    calc.symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "8");
+    calc.memory_mrc()?;
+    assert_eq!(calc.current_operand_to_str(), "3");
+    Ok(())
+}
+
+#[test]
+fn toggle_sign() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('5')?
+        .symbol_in('*')?
+        .toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "-5");
+    calc.symbol_in('3')?
+        .toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "-3");
+    calc.symbol_in('=')?
+        .toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "15");
+    calc.symbol_in('+')?
+        .symbol_in('1')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "16");
+
+    calc.erase_all()
+        .toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "0");
+    Ok(())
+}
+
+#[test]
+fn repeated_equals() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('5')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .symbol_in('=')?
+        .symbol_in('=')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "14");
+
+    calc.erase_all()
+        .symbol_in('3')?
+        .symbol_in('*')?
+        .symbol_in('5')?
+        .symbol_in('=')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "75");
+
+    calc.toggle_k_constant();
+    calc.erase_all()
+        .symbol_in('3')?
+        .symbol_in('*')?
+        .symbol_in('5')?
+        .symbol_in('=')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "45");
+
+    calc.toggle_k_constant();
+    calc.erase_all()
+        .symbol_in('1')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('*')?
+        .symbol_in('1')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('=')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "100000000000000");
+    assert!(calc.symbol_in('=').is_err());
+    assert_eq!(calc.current_operand_to_str(), "100000000000000");
+    Ok(())
+}
+
+#[test]
+fn angle_units() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.next_angle_unit();
+    calc.next_angle_unit();
+    calc.symbol_in('3')?
+        .symbol_in('0')?
+        .operator_in(Operator::Sine)?;
+    assert_eq!(calc.current_operand_to_str(), "0.5");
+    calc.next_angle_unit();
+    calc.next_angle_unit();
+    calc.symbol_in('1')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .operator_in(Operator::Cosine)?;
+    assert_eq!(calc.current_operand_to_str(), "0");
+    Ok(())
+}
+
+#[test]
+fn inverse_trigonometry() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.next_angle_unit();
+    calc.next_angle_unit();
+    calc.symbol_in('.')?
+        .symbol_in('5')?
+        .operator_in(Operator::ArcSine)?;
+    assert_eq!(calc.current_operand_to_str(), "30");
+    calc.symbol_in('1')?
+        .operator_in(Operator::ArcTangent)?;
+    assert_eq!(calc.current_operand_to_str(), "45");
+    calc.symbol_in('9')?
+        .symbol_in('0')?;
+    assert!(calc.operator_in(Operator::Tangent).is_err());
+    calc.erase_all()
+        .symbol_in('2')?;
+    assert!(calc.operator_in(Operator::ArcCosine).is_err());
+    Ok(())
+}
+
+#[test]
+fn hyperbolic() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('1')?
+        .symbol_in('0')?
+        .operator_in(Operator::HyperbolicSine)?;
+    assert_eq!(calc.current_operand_to_str(), "11013.23287470339338");
+    calc.operator_in(Operator::HyperbolicArcSine)?;
+    assert_eq!(calc.current_operand_to_str(), "10");
+    calc.toggle_sign();
+    calc.operator_in(Operator::HyperbolicSine)?;
+    assert_eq!(calc.current_operand_to_str(), "-11013.23287470339338");
+    calc.erase_all();
+    calc.set_digit_capacity(28)?;
+    calc.set_current_operand(dec!(10))?;
+    calc.operator_in(Operator::HyperbolicSine)?;
+    assert_eq!(calc.current_operand_to_str(), "11013.23287470339337723652");
+    calc.set_digit_capacity(16)?;
+    calc.set_current_operand(dec!(-100))?;
+    assert!(calc.operator_in(Operator::HyperbolicSine).is_err());
+    calc.erase_all();
+    calc.set_current_operand(dec!(-1000000000000000))?;
+    calc.operator_in(Operator::Exponential)?;
+    assert_eq!(calc.current_operand_to_str(), "0");
+    calc.symbol_in('1')?;
+    assert!(calc.operator_in(Operator::HyperbolicArcTangent).is_err());
+    Ok(())
+}
+
+#[test]
+fn roots_and_reciprocal() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('8')?
+        .operator_in(Operator::Root)?
+        .symbol_in('3')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "2");
+    calc.operator_in(Operator::Square)?
+        .operator_in(Operator::SquareRoot)?
+        .operator_in(Operator::Reciprocal)?;
+    assert_eq!(calc.current_operand_to_str(), "0.5");
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('7')?;
+    calc.toggle_sign();
+    calc.operator_in(Operator::Root)?
+        .symbol_in('3')?
+        .symbol_in('=')?
+        .operator_in(Operator::AbsoluteValue)?;
+    assert_eq!(calc.current_operand_to_str(), "3");
+    calc.toggle_sign();
+    assert!(matches!(calc.operator_in(Operator::SquareRoot),
+                     Err(CommandError::ComplexResult(Operator::SquareRoot, _))));
+    calc.erase_all();
+    calc.symbol_in('0')?;
+    assert!(matches!(calc.operator_in(Operator::Reciprocal), Err(CommandError::DivisionByZero)));
+    Ok(())
+}
+
+#[test]
+fn power_of_negative_base() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('8')?;
+    calc.toggle_sign();
+    calc.operator_in(Operator::Power)?;
+    calc.set_current_operand(dec!(0.33333333333333))?;
+    calc.symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "-2");
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('^')?
+        .symbol_in('0')?
+        .symbol_in('.')?
+        .symbol_in('5')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "1.4142135623731");
+    calc.erase_all();
+    calc.symbol_in('4')?;
+    calc.toggle_sign();
+    calc.symbol_in('^')?
+        .symbol_in('0')?
+        .symbol_in('.')?
+        .symbol_in('5')?;
+    assert!(matches!(calc.symbol_in('='),
+                     Err(CommandError::ComplexResult(Operator::Power, _))));
+    Ok(())
+}
+
+#[test]
+fn logarithms() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('3')?
+        .operator_in(Operator::DecimalExponential)?;
+    assert_eq!(calc.current_operand_to_str(), "1000");
+    calc.operator_in(Operator::DecimalLogarithm)?;
+    assert_eq!(calc.current_operand_to_str(), "3");
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .operator_in(Operator::Logarithm)?
+        .symbol_in('1')?
+        .symbol_in('0')?
+        .symbol_in('2')?
+        .symbol_in('4')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "10");
+    calc.erase_all();
+    calc.symbol_in('8')?
+        .operator_in(Operator::BinaryLogarithm)?;
+    assert_eq!(calc.current_operand_to_str(), "3");
+    calc.erase_all();
+    calc.symbol_in('1')?
+        .operator_in(Operator::Exponential)?;
+    assert_eq!(calc.current_operand_to_str(), "2.71828182845905");
+    calc.erase_all();
+    calc.symbol_in('0')?;
+    assert!(matches!(calc.operator_in(Operator::NaturalLogarithm),
+                     Err(CommandError::OutOfDomain(Operator::NaturalLogarithm, _))));
+    Ok(())
+}
+
+#[test]
+fn modulo_and_integer_division() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('7')?;
+    calc.toggle_sign();
+    calc.symbol_in('m')?
+        .symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "1");
+    calc.erase_all();
+    calc.symbol_in('7')?;
+    calc.toggle_sign();
+    calc.symbol_in('\\')?
+        .symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "-4");
+    calc.toggle_division_rounding();
+    calc.erase_all();
+    calc.symbol_in('7')?;
+    calc.toggle_sign();
+    calc.symbol_in('m')?
+        .symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "-1");
+    calc.erase_all();
+    calc.symbol_in('7')?;
+    calc.toggle_sign();
+    calc.symbol_in('\\')?
+        .symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "-3");
+    calc.symbol_in('m')?
+        .symbol_in('0')?;
+    assert!(matches!(calc.symbol_in('='), Err(CommandError::DivisionByZero)));
+    Ok(())
+}
+
+#[test]
+fn factorial() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('1')?
+        .symbol_in('8')?
+        .operator_in(Operator::Factorial)?;
+    assert_eq!(calc.current_operand_to_str(), "6402373705728000");
+    calc.erase_all();
+    calc.symbol_in('0')?
+        .symbol_in('.')?
+        .symbol_in('5')?
+        .operator_in(Operator::Factorial)?;
+    assert_eq!(calc.current_operand_to_str(), "0.88622692545276");
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('0')?;
+    assert!(matches!(calc.operator_in(Operator::Factorial), 
+                     Err(CommandError::OutOfBufferRange(value)) if value == dec!(2432902008176640000)));
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('8')?;
+    assert!(matches!(calc.operator_in(Operator::Factorial), Err(CommandError::OutOfBufferRange(_))));
+    calc.erase_all();
+    calc.set_current_operand(dec!(-30.5))?;
+    calc.operator_in(Operator::Factorial)?;
+    assert_eq!(calc.current_operand_to_str(), "0");
+    calc.erase_all();
+    calc.symbol_in('1')?;
+    calc.toggle_sign();
+    assert!(matches!(calc.operator_in(Operator::Factorial), Err(CommandError::OutOfDomain(..))));
+    Ok(())
+}
+
+#[test]
+fn combinatorics() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('5')?
+        .symbol_in('2')?
+        .operator_in(Operator::Combinations)?
+        .symbol_in('5')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "2598960");
+    calc.erase_all();
+    calc.symbol_in('1')?
+        .symbol_in('0')?
+        .operator_in(Operator::Permutations)?
+        .symbol_in('3')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "720");
+    calc.erase_all();
+    calc.symbol_in('1')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .operator_in(Operator::Combinations)?
+        .symbol_in('5')?
+        .symbol_in('0')?;
+    assert!(matches!(calc.symbol_in('='), Err(CommandError::OutOfBufferRange(_))));
+    calc.erase_all();
+    calc.symbol_in('5')?
+        .operator_in(Operator::Combinations)?
+        .symbol_in('0')?
+        .symbol_in('.')?
+        .symbol_in('5')?;
+    assert!(matches!(calc.symbol_in('='), Err(CommandError::OutOfDomain(..))));
+    Ok(())
+}
+
+#[test]
+fn algebraic_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('2')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .symbol_in('*')?
+        .symbol_in('4')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "20");
+    calc.toggle_algebraic();
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .symbol_in('*')?
+        .symbol_in('4')?
+        .symbol_in('^')?
+        .symbol_in('2')?
+        .symbol_in('-')?;
+    assert_eq!(calc.current_operand_to_str(), "50");
+    calc.symbol_in('1')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "49");
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('^')?
+        .symbol_in('3')?
+        .symbol_in('^')?
+        .symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "512");
+    // the replaced operator takes the precedence of the new one
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .symbol_in('*')?
+        .symbol_in('-')?
+        .symbol_in('1')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "4");
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('+')?
+        .symbol_in('3')?
+        .symbol_in('*')?
+        .symbol_in('4')?;
+    calc.undo();
+    calc.undo();
+    calc.symbol_in('+')?;
+    assert_eq!(calc.current_operand_to_str(), "5");
+    Ok(())
+}
+
+#[test]
+fn parentheses() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('2')?
+        .symbol_in('*')?
+        .symbol_in('(')?
+        .symbol_in('3')?
+        .symbol_in('+')?
+        .symbol_in('4')?;
+    assert_eq!(calc.nesting_depth(), 1);
+    calc.symbol_in(')')?;
+    assert_eq!(calc.nesting_depth(), 0);
+    assert_eq!(calc.current_operand_to_str(), "7");
+    calc.symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "14");
+    calc.erase_all();
+    calc.symbol_in('(')?
+        .symbol_in('(')?
+        .symbol_in('1')?
+        .symbol_in('+')?
+        .symbol_in('2')?
+        .symbol_in(')')?
+        .symbol_in('*')?
+        .symbol_in('3')?
+        .symbol_in('=')?;
+    assert_eq!(calc.nesting_depth(), 0);
+    assert_eq!(calc.current_operand_to_str(), "9");
+    assert!(matches!(calc.symbol_in(')'), Err(CommandError::UnbalancedParenthesis)));
+    Ok(())
+}
+
+#[test]
+fn programmer_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('7')?
+        .symbol_in('.')?
+        .symbol_in('5')?;
+    calc.toggle_programmer()?;
+    assert_eq!(calc.current_operand_to_str(), "7");
+    calc.symbol_in('/')?
+        .symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "3");
+    calc.erase_all();
+    calc.set_base(Base::Hexadecimal)?;
+    calc.symbol_in('f')?
+        .symbol_in('F')?
+        .symbol_in('g')?
+        .symbol_in('.')?
+        .symbol_in('+')?
+        .symbol_in('1')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "100");
+    calc.set_base(Base::Binary)?;
+    assert_eq!(calc.current_operand_to_str(), "100000000");
+    calc.symbol_in('2')?;
+    assert_eq!(calc.current_operand_to_str(), "0");
+    calc.symbol_in('1')?;
+    assert!(calc.operator_in(Operator::Sine).is_err());
+    calc.erase_all();
+    calc.set_base(Base::Octal)?;
+    calc.toggle_programmer()?;
+    calc.symbol_in('7')?
+        .symbol_in('.')?
+        .symbol_in('5')?;
+    assert_eq!(calc.current_operand_to_str(), "7.5");
+    Ok(())
+}
+
+#[test]
+fn programmer_word_limits() -> Result<(), CommandError> {
+    let mut calc = Calculator::new();
+    calc.toggle_programmer()?;
+    for _ in 0..20 {
+        calc.symbol_in('9')?;
+    }
+    assert_eq!(calc.current_operand_to_str(), "999999999999999999");
+    calc.erase_all();
+    calc.next_word_size()?;
+    calc.symbol_in('9')?
+        .symbol_in('9')?
+        .symbol_in('9')?;
+    assert_eq!(calc.current_operand_to_str(), "99");
+    calc.set_base(Base::Octal)?;
+    calc.erase_all();
+    calc.symbol_in('7')?
+        .symbol_in('7')?
+        .symbol_in('7')?;
+    assert_eq!(calc.current_operand_to_str(), "77");
+
+    // the largest unsigned quad word does not fit 16 decimal digits
+    calc.next_word_size()?;
+    calc.next_word_size()?;
+    calc.next_word_size()?;
+    calc.toggle_unsigned()?;
+    calc.set_base(Base::Decimal)?;
+    calc.erase_all();
+    calc.symbol_in('0')?
+        .symbol_in('-')?
+        .symbol_in('1')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "18446744073709551615");
+    assert!(calc.toggle_programmer().is_err());
+    assert!(calc.settings().programmer);
+    assert_eq!(calc.current_operand_to_str(), "18446744073709551615");
+    calc.erase_all();
+    calc.symbol_in('5')?;
+    calc.toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "18446744073709551611");
+    assert_eq!(calc.current_operand_to_dec(), dec!(18446744073709551611));
+    calc.toggle_unsigned()?;
+    assert_eq!(calc.current_operand_to_str(), "-5");
+    calc.toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "5");
+
+    // the right operand is negated in the word as well
+    calc.toggle_unsigned()?;
+    calc.erase_all()
+        .symbol_in('5')?
+        .symbol_in('*')?
+        .toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "18446744073709551611");
+    calc.symbol_in('3')?
+        .toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "18446744073709551613");
+    calc.symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "18446744073709551601");
+    calc.toggle_unsigned()?;
+    calc.set_base(Base::Hexadecimal)?;
+    calc.erase_all()
+        .symbol_in('5')?
+        .symbol_in('*')?
+        .toggle_sign();
+    assert_eq!(calc.current_operand_to_str(), "FFFFFFFFFFFFFFFB");
+    calc.symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "FFFFFFFFFFFFFFE7");
+    Ok(())
+}
+
+#[test]
+fn bitwise_operators() -> Result<(), CommandError> {
+    let mut calc = Calculator::new();
+    calc.toggle_programmer()?;
+    calc.set_base(Base::Hexadecimal)?;
+    calc.symbol_in('C')?
+        .symbol_in('&')?
+        .symbol_in('A')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "8");
+    calc.symbol_in('|')?
+        .symbol_in('3')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "B");
+    calc.symbol_in('<')?
+        .symbol_in('4')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "B0");
+    calc.operator_in(Operator::BitwiseNot)?;
+    assert_eq!(calc.current_operand_to_str(), "FFFFFFFFFFFFFF4F");
+    calc.set_base(Base::Decimal)?;
+    assert_eq!(calc.current_operand_to_str(), "-177");
+    calc.erase_all();
+
+    // 8 bit signed word
+    calc.next_word_size()?;
+    calc.symbol_in('1')?
+        .symbol_in('2')?
+        .symbol_in('7')?
+        .symbol_in('+')?
+        .symbol_in('1')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "-128");
+    calc.symbol_in('>')?
+        .symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "-32");
+    calc.toggle_unsigned()?;
+    assert_eq!(calc.current_operand_to_str(), "224");
+    calc.symbol_in('>')?
+        .symbol_in('2')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "56");
+    calc.set_base(Base::Binary)?;
+    calc.symbol_in('1')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('0')?
+        .symbol_in('1')?;
+    calc.operator_in(Operator::RotateLeft)?;
+    calc.symbol_in('1')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "11");
+    calc.operator_in(Operator::RotateRight)?;
+    calc.symbol_in('1')?
+        .symbol_in('0')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "11000000");
+    calc.operator_in(Operator::BitwiseXor)?;
+    calc.symbol_in('1')?
+        .symbol_in('1')?
+        .symbol_in('1')?
+        .symbol_in('1')?
+        .symbol_in('1')?
+        .symbol_in('1')?
+        .symbol_in('1')?
+        .symbol_in('1')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "111111");
+    calc.erase_all();
+    calc.toggle_programmer()?;
+    calc.operator_in(Operator::BitwiseAnd)?;
+    assert!(calc.symbol_in('1')?.symbol_in('=').is_err());
+    Ok(())
+}
+
+#[test]
+fn float_inspection() -> Result<(), CommandError> {
+    let mut calc = Calculator::new();
+    calc.symbol_in('0')?
+        .symbol_in('.')?
+        .symbol_in('1')?;
+    let single: FloatInspection = calc.inspect_float(Precision::Single)?;
+    assert_eq!(single.hex(), "3DCCCCCD");
+    assert_eq!(single.bits_string(), "0 01111011 10011001100110011001101");
+    assert_eq!(single.unbiased_exponent(), -4);
+    assert_eq!(single.error(), Some(dec!(0.000000001490116119384765625)));
+    let double: FloatInspection = calc.inspect_float(Precision::Double)?;
+    assert_eq!(double.hex(), "3FB999999999999A");
+    assert_eq!(double.exponent(), 1019);
+    let error: Decimal = double.error().ok_or(CommandError::Overflow)?;
+    assert!(error > Decimal::ZERO);
+    assert!(error < dec!(0.00000000000000001));
+    calc.toggle_sign();
+    assert!(calc.inspect_float(Precision::Double)?.is_negative());
+    calc.erase_all();
+    calc.toggle_programmer()?;
+    calc.set_base(Base::Hexadecimal)?;
+    calc.symbol_in('4')?
+        .symbol_in('0')?;
+    let single: FloatInspection = calc.inspect_float(Precision::Single)?;
+    assert_eq!(single.hex(), "42800000");
+    assert_eq!(single.error(), Some(Decimal::ZERO));
+
+    calc.toggle_programmer()?;
+    calc.set_current_operand(dec!(0.0000000000000000000000001))?;
+    let double: FloatInspection = calc.inspect_float(Precision::Double)?;
+    assert_eq!(double.error(), None);
+    assert_eq!(double.error_string(), "< 1e-28");
+    Ok(())
+}
+
+#[test]
+fn digit_capacity() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    assert!(calc.set_digit_capacity(9).is_err());
+    calc.set_digit_capacity(8)?;
+    for _ in 0..9 {
+        calc.symbol_in('9')?;
+    }
+    assert_eq!(calc.current_operand_to_str(), "99999999");
+    calc.symbol_in('+')?
+        .symbol_in('1')?;
+    assert!(calc.symbol_in('=').is_err());
+    calc.erase_all();
+    calc.symbol_in('2')?
+        .symbol_in('^')?
+        .symbol_in('0')?
+        .symbol_in('.')?
+        .symbol_in('5')?
+        .symbol_in('=')?;
+    assert_eq!(calc.current_operand_to_str(), "1.414214");
+
+    calc.set_digit_capacity(28)?;
+    calc.erase_all();
+    for _ in 0..28 {
+        calc.symbol_in('9')?;
+    }
+    assert_eq!(calc.current_operand_to_str(), "9999999999999999999999999999");
+    assert!(calc.set_digit_capacity(16).is_err());
+    assert_eq!(calc.settings().digits.get(), 28);
+    assert_eq!(calc.current_operand_to_str(), "9999999999999999999999999999");
+    calc.memory_add()?;
+    calc.erase_all();
+    assert!(calc.next_digit_capacity().is_err());
+    assert_eq!(calc.settings().digits.get(), 28);
+    calc.memory_mrc()?;
+    calc.memory_mrc()?;
+    calc.erase_all();
+    calc.set_digit_capacity(8)?;
+    assert!(calc.set_current_operand(dec!(123456789)).is_err());
+    assert_eq!(calc.current_operand_to_str(), "0");
+    calc.set_digit_capacity(16)?;
+    calc.erase_all();
+    assert_eq!(calc.settings().digits.get(), 16);
+    calc.next_digit_capacity()?;
+    assert_eq!(calc.settings().digits.get(), 20);
+
+    let settings: Settings = serde_json::from_str(r#"{"digits":20}"#)?;
+    assert_eq!(settings.digits.get(), 20);
+    assert!(serde_json::from_str::<Settings>(r#"{"digits":40}"#).is_err());
+    assert!(serde_json::from_str::<Settings>(r#"{"digits":1}"#).is_err());
+    Ok(())
+}
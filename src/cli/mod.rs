@@ -0,0 +1,170 @@
+use std::io::{self, BufRead, Write};
+
+use crate::calculator::{Calculator, error::CommandError, operator::Operator, settings::{Base, Radix}, float::{FloatInspection, Precision}};
+
+#[cfg(test)]
+mod tests;
+
+/// Reads lines from stdin and prints the calculator display after each of them.
+///
+/// Every line is split by whitespace into tokens. A token is either a command
+/// word (see [`execute_word`]) or a sequence of keys, the same ones the GUI
+/// accepts from the keyboard (see [`key_in`]).
+pub fn repl() -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    writeln!(stdout, "{}", calc.current_operand_to_str())?;
+    for line in stdin.lock().lines() {
+        let line: String = line?;
+        if matches!(line.trim(), "q" | "quit" | "exit") {
+            break;
+        }
+        let response: Result<(), CommandError> = line
+            .split_whitespace()
+            .try_for_each(|token| execute(&mut calc, token));
+        match response {
+            Ok(_) => writeln!(stdout, "{}", calc.current_operand_to_str())?,
+            Err(err) => {
+                calc.erase_all();
+                writeln!(stdout, "e {}", err.to_string())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replays the keys at once and prints the resulting display.
+///
+/// Fails on the first [`CommandError`], so the process exits with non-zero code.
+pub fn eval(keys: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut calc = Calculator::new();
+    keys.split_whitespace()
+        .try_for_each(|token| execute(&mut calc, token))?;
+    println!("{}", calc.current_operand_to_str());
+    Ok(())
+}
+
+fn execute(calc: &mut Calculator, token: &str) -> Result<(), CommandError> {
+    if let Some(response) = execute_word(calc, token) {
+        return response
+    }
+    token.chars().try_for_each(|key| key_in(calc, key))
+}
+
+/// Commands which have no single key in the GUI
+fn execute_word(calc: &mut Calculator, word: &str) -> Option<Result<(), CommandError>> {
+    let response: Result<(), CommandError> = match word {
+        "mrc" => calc.memory_mrc(),
+        "m+" => calc.memory_add(),
+        "m-" => calc.memory_sub(),
+        "del" => {
+            calc.erase();
+            Ok(())
+        },
+        "ac" => {
+            calc.erase_all();
+            Ok(())
+        },
+        "undo" => {
+            calc.undo();
+            Ok(())
+        },
+        "redo" => {
+            calc.redo();
+            Ok(())
+        },
+        "k" => {
+            calc.toggle_k_constant();
+            Ok(())
+        },
+        "drg" => {
+            calc.next_angle_unit();
+            Ok(())
+        },
+        "alg" => {
+            calc.toggle_algebraic();
+            Ok(())
+        },
+        "prg" => calc.toggle_programmer(),
+        "dec" => calc.set_base(Base::Decimal),
+        "hex" => calc.set_base(Base::Hexadecimal),
+        "oct" => calc.set_base(Base::Octal),
+        "bin" => calc.set_base(Base::Binary),
+        "dig" => calc.next_digit_capacity(),
+        "word" => calc.next_word_size(),
+        "uns" => calc.toggle_unsigned(),
+        "xor" => calc.operator_in(Operator::BitwiseXor).map(|_| ()),
+        "rol" => calc.operator_in(Operator::RotateLeft).map(|_| ()),
+        "ror" => calc.operator_in(Operator::RotateRight).map(|_| ()),
+        "trunc" => {
+            calc.toggle_division_rounding();
+            Ok(())
+        },
+        "sinh" => calc.operator_in(Operator::HyperbolicSine).map(|_| ()),
+        "cosh" => calc.operator_in(Operator::HyperbolicCosine).map(|_| ()),
+        "tanh" => calc.operator_in(Operator::HyperbolicTangent).map(|_| ()),
+        "asinh" => calc.operator_in(Operator::HyperbolicArcSine).map(|_| ()),
+        "acosh" => calc.operator_in(Operator::HyperbolicArcCosine).map(|_| ()),
+        "atanh" => calc.operator_in(Operator::HyperbolicArcTangent).map(|_| ()),
+        "sqr" => calc.operator_in(Operator::Square).map(|_| ()),
+        "abs" => calc.operator_in(Operator::AbsoluteValue).map(|_| ()),
+        "log2" => calc.operator_in(Operator::BinaryLogarithm).map(|_| ()),
+        "loga" => calc.operator_in(Operator::Logarithm).map(|_| ()),
+        "ncr" => calc.operator_in(Operator::Combinations).map(|_| ()),
+        "npr" => calc.operator_in(Operator::Permutations).map(|_| ()),
+        "tape" => {
+            calc.tape()
+                .entries()
+                .iter()
+                .for_each(|entry| println!("{}", entry));
+            Ok(())
+        },
+        "ieee" => Precision::ALL.iter()
+            .try_for_each(|&precision| {
+                let inspection: FloatInspection = calc.inspect_float(precision)?;
+                println!("{} {} {} error {}",
+                         precision,
+                         inspection.hex(),
+                         inspection.bits_string(),
+                         inspection.error_string());
+                Ok(())
+            }),
+        _ => return None
+    };
+    Some(response)
+}
+
+/// Mirrors the keyboard bindings of the GUI
+fn key_in(calc: &mut Calculator, key: char) -> Result<(), CommandError> {
+    match key {
+        // letters are digits in hexadecimal
+        'a'..='f' | 'A'..='F' if matches!(calc.settings().radix(),
+                                          Some(Radix { base: Base::Hexadecimal, .. }))
+            => calc.symbol_in(key).map(|_| ()),
+        '0'..='9' | '.' | ',' | '+' | '-' | '*' | '/' | '^' | 'm' | '\\' | '&' | '|' | '<' | '>' | '(' | ')' | '=' => calc.symbol_in(key).map(|_| ()),
+        '%' => calc.percentage(),
+        '!' => calc.operator_in(Operator::Factorial).map(|_| ()),
+        '~' => calc.operator_in(Operator::BitwiseNot).map(|_| ()),
+        's' => calc.operator_in(Operator::Sine).map(|_| ()),
+        'c' => calc.operator_in(Operator::Cosine).map(|_| ()),
+        'l' => calc.operator_in(Operator::NaturalLogarithm).map(|_| ()),
+        'L' => calc.operator_in(Operator::Exponential).map(|_| ()),
+        'g' => calc.operator_in(Operator::DecimalLogarithm).map(|_| ()),
+        'G' => calc.operator_in(Operator::DecimalExponential).map(|_| ()),
+        't' => calc.operator_in(Operator::Tangent).map(|_| ()),
+        'S' => calc.operator_in(Operator::ArcSine).map(|_| ()),
+        'C' => calc.operator_in(Operator::ArcCosine).map(|_| ()),
+        'T' => calc.operator_in(Operator::ArcTangent).map(|_| ()),
+        'r' => calc.operator_in(Operator::SquareRoot).map(|_| ()),
+        'R' => calc.operator_in(Operator::Root).map(|_| ()),
+        'i' => calc.operator_in(Operator::Reciprocal).map(|_| ()),
+        'p' => calc.pi(),
+        'n' => {
+            calc.toggle_sign();
+            Ok(())
+        },
+        'e' => calc.eulers_number(),
+        _ => Err(CommandError::IncorrectOperation(format!("Unknown key: {}", key)))
+    }
+}
@@ -0,0 +1,30 @@
+use crate::calculator::{Calculator, error::CommandError};
+
+use super::execute;
+
+fn run(keys: &str) -> Result<Calculator, CommandError> {
+    let mut calc = Calculator::new();
+    keys.split_whitespace()
+        .try_for_each(|token| execute(&mut calc, token))?;
+    Ok(calc)
+}
+
+#[test]
+fn keys() -> Result<(), CommandError> {
+    assert_eq!(run("12+7*3=")?.current_operand_to_str(), "57");
+    assert_eq!(run("alg 12+7*3=")?.current_operand_to_str(), "33");
+    assert_eq!(run("9 sqr")?.current_operand_to_str(), "81");
+    assert_eq!(run("0c")?.current_operand_to_str(), "1");
+    assert!(run("12 foo").is_err());
+    assert!(run("12?").is_err());
+    Ok(())
+}
+
+#[test]
+fn hexadecimal_digits() -> Result<(), CommandError> {
+    assert_eq!(run("prg hex abc+DEF=")?.current_operand_to_str(), "18AB");
+    assert_eq!(run("prg hex f dec")?.current_operand_to_str(), "15");
+    // letters are commands outside of hexadecimal
+    assert!(run("prg a").is_err());
+    Ok(())
+}
@@ -1,14 +1,14 @@
-use std::{
-    cell::{Ref, RefCell}, 
-    str::FromStr
-};
+use std::cell::{Ref, RefCell};
+#[cfg(not(target_arch = "wasm32"))]
+use std::str::FromStr;
 
+#[cfg(not(target_arch = "wasm32"))]
 use arboard::Clipboard;
 use iced::{
     Application, 
     Theme, 
     executor, 
-    widget::{button, text, column, container, Container, Column, row, Row, Space, Text}, 
+    widget::{button, text, column, container, scrollable, Container, Column, row, Row, Text}, 
     Command, 
     Settings, 
     keyboard::{
@@ -18,21 +18,34 @@ use iced::{
     Event, 
     alignment::{self, Horizontal}, 
     Length, 
-    window::{self, Icon}
+    window::{self, Icon}, 
+    theme, 
+    Element
 };
+#[cfg(not(target_arch = "wasm32"))]
 use image::ImageFormat;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, prelude::ToPrimitive};
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::clipboard;
 use crate::{
-    calculator::{Calculator, operand::Operand, error::CommandError, self, operator::Operator}, 
-    clipboard
+    calculator::{Calculator, operand::Operand, error::CommandError, self, operator::Operator, settings::{DivisionRounding, Base, Radix, WordSize, DigitCapacity}, float::{FloatInspection, Precision}}, 
+    storage
 };
 
 #[cfg(test)]
 mod tests;
 
+// width of the calculator itself, the rest of the window is taken by 
+// the scientific functions on the left and the tape on the right
+const CALC_WIDTH: u16 = 400;
+const SCIENTIFIC_WIDTH: u16 = 280;
+const TAPE_WIDTH: u16 = 240;
+// the line of 16 decimal digits with signs and indicators fits the display
+const LONG_LINE_LENGTH: usize = 24;
+
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    const WIDTH: u32 = 400; // 260
+    const WIDTH: u32 = SCIENTIFIC_WIDTH as u32 + CALC_WIDTH as u32 + TAPE_WIDTH as u32;
     const HEIGHT: u32 = 260;
     Ok(CalcState::run(Settings {
         window: window::Settings {
@@ -40,19 +53,41 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             min_size: Some((WIDTH, HEIGHT)),
             max_size: Some((WIDTH, HEIGHT)),
             //resizable: false,
-            icon: Some(
-                Icon::from_file_data(include_bytes!("../../calculator-48.png"), 
-                Some(ImageFormat::Png))?),
+            icon: window_icon()?,
             ..Default::default()
         },
+        // the session is saved before exit
+        exit_on_close_request: false,
         ..Default::default()
     })?)
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn window_icon() -> Result<Option<Icon>, Box<dyn std::error::Error>> {
+    Ok(Some(
+        Icon::from_file_data(include_bytes!("../../calculator-48.png"), 
+        Some(ImageFormat::Png))?))
+}
+
+// the page has its own favicon
+#[cfg(target_arch = "wasm32")]
+fn window_icon() -> Result<Option<Icon>, Box<dyn std::error::Error>> {
+    Ok(None)
+}
+
 #[derive(Debug)]
 struct CalcState {
     calc: RefCell<Calculator>,
     line: RefCell<String>,
+    should_exit: bool,
+    /// HYP key turns trigonometric operators into hyperbolic ones
+    hyperbolic: bool,
+    /// Current value in all bases, empty unless it is an integer
+    readout: String,
+    /// IEEE 754 layout of the current value is shown instead of the tape
+    inspector: bool,
+    /// Revision of the calculator the session is saved at
+    saved_revision: u64,
 }
 
 impl Default for CalcState {
@@ -62,7 +97,12 @@ impl Default for CalcState {
             // 'e' + 'M' + space + '-' + '.' + operand buffer
             line: RefCell::new(
                 String::with_capacity(
-                    1 + 1 + 1 + 1 + 1 + calculator::BUFFER_SIZE)), 
+                    1 + 1 + 1 + 1 + 1 + DigitCapacity::MAX)), 
+            should_exit: false,
+            hyperbolic: false,
+            readout: String::new(),
+            inspector: false,
+            saved_revision: 0,
         }
     }
 } 
@@ -81,12 +121,31 @@ enum CalcMessage {
     Sine,
     Cosine,
     Pi,
-    _Set(Decimal),
+    Set(Decimal),
     Percentage,
     EulersNumber,
     Copy,
     Paste,
+    Undo,
+    Redo,
+    ClearTape,
+    ToggleInspector,
+    ToggleSign,
+    ToggleKConstant,
+    NextAngleUnit,
+    ToggleDivisionRounding,
+    ToggleAlgebraic,
+    ToggleHyperbolic,
+    ToggleProgrammer,
+    SetBase(Base),
+    NextWordSize,
+    ToggleUnsigned,
+    NextDigitCapacity,
+    /// Key of A-F, its meaning depends on the mode
+    Letter(char, Modifiers),
+    Exit,
 
+    Apply(Operator),
     Symbol(char),
 }
 
@@ -98,7 +157,10 @@ impl Application for CalcState {
 
     fn new(_: Self::Flags) -> (Self, iced::Command<Self::Message>) {
         let mut state = CalcState::default();
-        state.line.get_mut().push('0');
+        if let Some(calc) = storage::load_session() {
+            state.calc = RefCell::new(calc);
+        }
+        state.update_state_from_calc(Ok(()));
         (state, Command::none())
     }
 
@@ -112,14 +174,8 @@ impl Application for CalcState {
             CalcMessage::Nothing => {}
 
             CalcMessage::MRC => {
-                let calc = self.calc.get_mut();
-                match calc.memory_mrc() {
-                    Ok(_) => {},
-                    Err(err) => {
-                        dbg!(&err);
-                    },
-                }
-                self.update_state_from_calc(Ok(()));
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().memory_mrc();
+                self.update_state_from_calc(calc_response);
             },
             
             CalcMessage::MMinus => {
@@ -167,24 +223,44 @@ impl Application for CalcState {
             },
 
             CalcMessage::Sine => {
+                let operator: Operator = self.modified(Operator::Sine);
                 let calc_ref = self.calc.get_mut();
-                let calc_response: Result<(), CommandError> = calc_ref.operator_in(Operator::Sine).map(|_| ());
+                let calc_response: Result<(), CommandError> = calc_ref.operator_in(operator).map(|_| ());
                 self.update_state_from_calc(calc_response);
             },
 
             CalcMessage::Cosine => {
+                let operator: Operator = self.modified(Operator::Cosine);
                 let calc_ref = self.calc.get_mut();
-                let calc_response: Result<(), CommandError> = calc_ref.operator_in(Operator::Cosine).map(|_| ());
+                let calc_response: Result<(), CommandError> = calc_ref.operator_in(operator).map(|_| ());
                 self.update_state_from_calc(calc_response);
             },
 
-            CalcMessage::Pi => {
+            CalcMessage::Apply(operator) => {
+                let operator: Operator = self.modified(operator);
                 let calc_ref = self.calc.get_mut();
-                calc_ref.pi();
-                self.update_state_from_calc(Ok(()));
+                let calc_response: Result<(), CommandError> = calc_ref.operator_in(operator).map(|_| ());
+                self.update_state_from_calc(calc_response);
             },
 
-            CalcMessage::_Set(_) => {},
+            CalcMessage::Pi => {
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().pi();
+                self.update_state_from_calc(calc_response);
+            },
+
+            CalcMessage::Set(value) => {
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().set_current_operand(value);
+                self.update_state_from_calc(calc_response);
+            },
+
+            CalcMessage::ClearTape => {
+                self.calc.get_mut().clear_tape();
+            },
+
+            CalcMessage::Exit => {
+                self.save_session();
+                self.should_exit = true;
+            },
 
             CalcMessage::Percentage => {
                 let calc_ref = self.calc.get_mut();
@@ -193,11 +269,11 @@ impl Application for CalcState {
             },
 
             CalcMessage::EulersNumber => {
-                let calc_ref = self.calc.get_mut();
-                calc_ref.eulers_number();
-                self.update_state_from_calc(Ok(()));
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().eulers_number();
+                self.update_state_from_calc(calc_response);
             },
 
+            #[cfg(not(target_arch = "wasm32"))]
             CalcMessage::Copy => {
                 let calc: Ref<Calculator> = self.calc.borrow();
                 let current_operand_str: String = calc.current_operand_to_str();
@@ -214,6 +290,7 @@ impl Application for CalcState {
                 }
             },
 
+            #[cfg(not(target_arch = "wasm32"))]
             CalcMessage::Paste => {
                 let calc: &mut Calculator = self.calc.get_mut();
                 let mut clipboard_ref = clipboard::CLIPBOARD.lock().unwrap();
@@ -232,10 +309,88 @@ impl Application for CalcState {
                 } else {
                     return Command::none()
                 };
-                calc.set_current_operand(parsed_number);
+                let calc_response: Result<(), CommandError> = calc.set_current_operand(parsed_number);
+                self.update_state_from_calc(calc_response);
+            },
+
+            #[cfg(target_arch = "wasm32")]
+            CalcMessage::Copy | CalcMessage::Paste => {},
+
+            CalcMessage::ToggleSign => {
+                let calc_ref = self.calc.get_mut();
+                calc_ref.toggle_sign();
+                self.update_state_from_calc(Ok(()));
+            },
+
+            CalcMessage::ToggleKConstant => {
+                self.calc.get_mut().toggle_k_constant();
+            },
+
+            CalcMessage::NextAngleUnit => {
+                self.calc.get_mut().next_angle_unit();
+            },
+
+            CalcMessage::ToggleAlgebraic => {
+                self.calc.get_mut().toggle_algebraic();
+            },
+
+            CalcMessage::ToggleDivisionRounding => {
+                self.calc.get_mut().toggle_division_rounding();
+            },
+
+            CalcMessage::ToggleInspector => {
+                self.inspector = !self.inspector;
+            },
+
+            CalcMessage::ToggleHyperbolic => {
+                self.hyperbolic = !self.hyperbolic;
+            },
+
+            CalcMessage::ToggleProgrammer => {
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().toggle_programmer();
+                self.update_state_from_calc(calc_response);
+            },
+
+            CalcMessage::SetBase(base) => {
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().set_base(base);
+                self.update_state_from_calc(calc_response);
+            },
+
+            CalcMessage::NextWordSize => {
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().next_word_size();
+                self.update_state_from_calc(calc_response);
+            },
+
+            CalcMessage::ToggleUnsigned => {
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().toggle_unsigned();
+                self.update_state_from_calc(calc_response);
+            },
+
+            CalcMessage::NextDigitCapacity => {
+                let calc_response: Result<(), CommandError> = self.calc.get_mut().next_digit_capacity();
+                self.update_state_from_calc(calc_response);
+            },
+
+            CalcMessage::Letter(letter, modifiers) => {
+                let message: CalcMessage = self.letter_command(letter, modifiers);
+                return self.update(message)
+            },
+
+            CalcMessage::Undo => {
+                let calc_ref = self.calc.get_mut();
+                calc_ref.undo();
+                self.update_state_from_calc(Ok(()));
+            },
+
+            CalcMessage::Redo => {
+                let calc_ref = self.calc.get_mut();
+                calc_ref.redo();
                 self.update_state_from_calc(Ok(()));
             },
         }
+        // a page is closed without any event, so it is saved after every change
+        #[cfg(target_arch = "wasm32")]
+        self.save_session();
         Command::none()
     }
 
@@ -245,8 +400,15 @@ impl Application for CalcState {
         //     text(self.number).size(50),
         //     button("-").on_press(CalcMessage::Dec)
         // ];
+        // long binary numbers of the programmer mode are squeezed
+        let line_length: usize = self.line.borrow().chars().count();
+        let text_size: u16 = if line_length > LONG_LINE_LENGTH {
+            (CALC_WIDTH as usize * 3 / (2 * line_length)) as u16
+        } else {
+            40
+        };
         let calc_txt: iced::Element<CalcMessage> = text(self.line.borrow())
-            .size(40)
+            .size(text_size)
             .horizontal_alignment(Horizontal::Right)
             .width(Length::Fill)
             // .width(Length::Shrink)
@@ -292,7 +454,7 @@ impl Application for CalcState {
                                            button(Self::btn_text("÷"))
                                                .width(Length::FillPortion(1))
                                                .on_press(CalcMessage::Symbol('/')), 
-                                           button(Self::btn_text("sin"))
+                                           button(Self::btn_text(self.modified(Operator::Sine).symbol()))
                                                .width(Length::FillPortion(1))
                                                .on_press(CalcMessage::Sine))
                                         .spacing(5);
@@ -313,7 +475,7 @@ impl Application for CalcState {
                                            button(Self::btn_text("-"))
                                                .width(Length::FillPortion(1))
                                                .on_press(CalcMessage::Symbol('-')), 
-                                           button(Self::btn_text("cos"))
+                                           button(Self::btn_text(self.modified(Operator::Cosine).symbol()))
                                                .width(Length::FillPortion(1))
                                                .on_press(CalcMessage::Cosine))
                                         .spacing(5);
@@ -341,18 +503,35 @@ impl Application for CalcState {
         let row_5: Row<CalcMessage> = row!(button(Self::btn_text("0"))
                                                .width(Length::FillPortion(1))
                                                .on_press(CalcMessage::Symbol('0')),  
-                                           Space::new(Length::FillPortion(1), Length::Shrink),
+                                           button(Self::btn_text("±"))
+                                               .width(Length::FillPortion(1))
+                                               .on_press(CalcMessage::ToggleSign),
                                            button(Self::btn_text("."))
                                                .width(Length::FillPortion(1))
                                                .on_press(CalcMessage::Symbol('.')),               
-                                           Space::new(Length::FillPortion(1), Length::Shrink),
-                                           Space::new(Length::FillPortion(1), Length::Shrink),
+                                           button(Self::btn_text("K"))
+                                               .width(Length::FillPortion(1))
+                                               .on_press(CalcMessage::ToggleKConstant),
+                                           button(Self::btn_text("DRG"))
+                                               .width(Length::FillPortion(1))
+                                               .on_press(CalcMessage::NextAngleUnit),
                                            button(Self::btn_text("="))
                                                .width(Length::FillPortion(1))
                                                .on_press(CalcMessage::Symbol('=')))
                                         .spacing(5);
+        let display: Row<CalcMessage> = row!(text(self.indicators()).size(14), calc_txt);
+        // binary digits of 28 decimal ones take 93 cells
+        let readout_length: usize = self.readout.lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(1);
+        let readout: Text = text(&self.readout)
+            .size((CALC_WIDTH as usize * 3 / (2 * readout_length)).min(10) as u16)
+            .horizontal_alignment(Horizontal::Right)
+            .width(Length::Fill);
         let main_col: Column<CalcMessage> = column!(
-            calc_txt,
+            display,
+            readout,
             row_1,
             row_2,
             row_3,
@@ -363,20 +542,29 @@ impl Application for CalcState {
             .spacing(5)
             .height(Length::Shrink);
         let c: Container<CalcMessage> = container(main_col)
-            .width(Length::Fill)
+            .width(Length::Units(CALC_WIDTH))
             .height(Length::Fill)
             .align_x(Horizontal::Center)
             .align_y(alignment::Vertical::Bottom)
             .center_x()
             .center_y()
             .padding(10);
-        c.into()
+        let side_panel: Container<CalcMessage> = if self.calc.borrow().settings().programmer {
+            self.programmer_view()
+        } else {
+            self.scientific_view()
+        };
+        row!(side_panel, c, self.tape_view()).into()
     }
 
 
     fn subscription(&self) -> iced::Subscription<Self::Message> {
         subscription::events().map(Self::on_event)
     }
+
+    fn should_exit(&self) -> bool {
+        self.should_exit
+    }
 }
 
 impl CalcState {
@@ -390,29 +578,35 @@ impl CalcState {
             // },
 
             Event::Keyboard(keyboard::Event::KeyPressed { 
-                key_code: KeyCode::D, 
-                modifiers: _ 
-            }) => Nothing,
+                key_code: KeyCode::C, 
+                modifiers: Modifiers::CTRL 
+            }) => Copy,
+
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: key_code @ (KeyCode::A | KeyCode::B | KeyCode::C
+                                      | KeyCode::D | KeyCode::E | KeyCode::F), 
+                modifiers 
+            }) => Letter(hex_letter(key_code), modifiers),
 
             Event::Keyboard(keyboard::Event::KeyPressed { 
-                key_code: KeyCode::A, 
+                key_code: KeyCode::H, 
                 modifiers: _ 
-            }) => Nothing,
+            }) => ToggleHyperbolic,
 
             Event::Keyboard(keyboard::Event::KeyPressed { 
-                key_code: KeyCode::C, 
-                modifiers: Modifiers::CTRL 
-            }) => Copy,
+                key_code: KeyCode::G, 
+                modifiers: Modifiers::SHIFT 
+            }) => Apply(Operator::DecimalExponential),
 
             Event::Keyboard(keyboard::Event::KeyPressed { 
-                key_code: KeyCode::C, 
+                key_code: KeyCode::G, 
                 modifiers: _ 
-            }) => Cosine,
+            }) => Apply(Operator::DecimalLogarithm),
 
             Event::Keyboard(keyboard::Event::KeyPressed { 
-                key_code: KeyCode::E, 
-                modifiers: _ 
-            }) => EulersNumber,
+                key_code: KeyCode::L, 
+                modifiers: Modifiers::SHIFT 
+            }) => Apply(Operator::Exponential),
 
             Event::Keyboard(keyboard::Event::KeyPressed { 
                 key_code: KeyCode::L, 
@@ -424,16 +618,67 @@ impl CalcState {
                 modifiers: _ 
             }) => Pi,
 
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::I, 
+                modifiers: _ 
+            }) => Apply(Operator::Reciprocal),
+
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::R, 
+                modifiers: Modifiers::SHIFT 
+            }) => Apply(Operator::Root),
+
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::R, 
+                modifiers: _ 
+            }) => Apply(Operator::SquareRoot),
+
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::S, 
+                modifiers: Modifiers::SHIFT 
+            }) => Apply(Operator::ArcSine),
+
             Event::Keyboard(keyboard::Event::KeyPressed { 
                 key_code: KeyCode::S, 
                 modifiers: _ 
             }) => Sine,
 
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::T, 
+                modifiers: Modifiers::SHIFT 
+            }) => Apply(Operator::ArcTangent),
+
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::T, 
+                modifiers: _ 
+            }) => Apply(Operator::Tangent),
+
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::F9, 
+                modifiers: _ 
+            }) => ToggleSign,
+
+            // Ctrl+N and the like are left to the system
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::N, 
+                modifiers 
+            }) if modifiers.is_empty() => ToggleSign,
+
             Event::Keyboard(keyboard::Event::KeyPressed { 
                 key_code: KeyCode::V, 
                 modifiers: Modifiers::CTRL 
             }) => Paste,
 
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::Z, 
+                modifiers: Modifiers::CTRL 
+            }) => Undo,
+
+            Event::Keyboard(keyboard::Event::KeyPressed { 
+                key_code: KeyCode::Y, 
+                modifiers: Modifiers::CTRL 
+            }) => Redo,
+
             Event::Keyboard(keyboard::Event::KeyPressed { 
                 key_code: KeyCode::Key5, 
                 modifiers: Modifiers::SHIFT // percentage
@@ -453,8 +698,15 @@ impl CalcState {
                 if ch == '%' {
                     return Percentage
                 }
+                if ch == '!' {
+                    return Apply(Operator::Factorial)
+                }
+                if ch == '~' {
+                    return Apply(Operator::BitwiseNot)
+                }
                 const COMMAND_CHARS: &[char] = &['1', '2', '3', '4', '5', '6', '7', '8', '9', '0', 
-                                                                '^', '=', '.', '-', '+', '*', '/'];
+                                                                '^', '=', '.', '-', '+', '*', '/', 
+                                                                'm', '\\', '(', ')', '&', '|', '<', '>'];
                 if COMMAND_CHARS.contains(&ch) {
                     Symbol(ch)
                 } else {
@@ -464,11 +716,229 @@ impl CalcState {
 
             Event::Keyboard(_) => Nothing,
             Event::Mouse(_) => Nothing,
+            Event::Window(window::Event::CloseRequested) => Exit,
             Event::Window(_) => Nothing,
             Event::Touch(_) => Nothing,
         }
     }
 
+    /// Functions which do not fit into the basic keypad
+    fn scientific_view(&self) -> Container<CalcMessage> {
+        use CalcMessage::*;
+        let buttons: &[&[(&str, CalcMessage)]] = &[
+            &[("HYP", ToggleHyperbolic),
+              (self.modified(Operator::Tangent).symbol(), Apply(Operator::Tangent)),
+              (self.modified(Operator::ArcSine).symbol(), Apply(Operator::ArcSine)),
+              (self.modified(Operator::ArcCosine).symbol(), Apply(Operator::ArcCosine)),
+              (self.modified(Operator::ArcTangent).symbol(), Apply(Operator::ArcTangent))],
+            &[("√x", Apply(Operator::SquareRoot)),
+              ("ʸ√x", Apply(Operator::Root)),
+              ("1/x", Apply(Operator::Reciprocal)),
+              ("x²", Apply(Operator::Square)),
+              ("|x|", Apply(Operator::AbsoluteValue))],
+            &[("log", Apply(Operator::DecimalLogarithm)),
+              ("log₂", Apply(Operator::BinaryLogarithm)),
+              ("logₐb", Apply(Operator::Logarithm)),
+              ("eˣ", Apply(Operator::Exponential)),
+              ("10ˣ", Apply(Operator::DecimalExponential))],
+            &[("x!", Apply(Operator::Factorial)),
+              ("nCr", Apply(Operator::Combinations)),
+              ("nPr", Apply(Operator::Permutations)),
+              ("(", Symbol('(')),
+              (")", Symbol(')'))],
+            &[("ALG", ToggleAlgebraic),
+              ("TRUNC", ToggleDivisionRounding),
+              ("PRG", ToggleProgrammer),
+              ("mod", Apply(Operator::Modulo)),
+              ("div", Apply(Operator::IntegerDivision))],
+            &[("DIG", NextDigitCapacity)],
+        ];
+        container(Self::buttons_grid(buttons))
+            .width(Length::Units(SCIENTIFIC_WIDTH))
+            .height(Length::Fill)
+            .align_y(alignment::Vertical::Bottom)
+            .padding(10)
+    }
+
+    /// Replaces the scientific functions in the programmer mode
+    fn programmer_view(&self) -> Container<CalcMessage> {
+        use CalcMessage::*;
+        let word_size: WordSize = self.calc.borrow().settings().word_size;
+        let buttons: &[&[(&str, CalcMessage)]] = &[
+            &[("DEC", SetBase(Base::Decimal)),
+              ("HEX", SetBase(Base::Hexadecimal)),
+              ("OCT", SetBase(Base::Octal)),
+              ("BIN", SetBase(Base::Binary)),
+              ("SCI", ToggleProgrammer)],
+            &[("A", Symbol('A')),
+              ("B", Symbol('B')),
+              ("C", Symbol('C'))],
+            &[("D", Symbol('D')),
+              ("E", Symbol('E')),
+              ("F", Symbol('F'))],
+            &[("AND", Apply(Operator::BitwiseAnd)),
+              ("OR", Apply(Operator::BitwiseOr)),
+              ("XOR", Apply(Operator::BitwiseXor)),
+              ("NOT", Apply(Operator::BitwiseNot))],
+            &[("<<", Apply(Operator::ShiftLeft)),
+              (">>", Apply(Operator::ShiftRight)),
+              ("ROL", Apply(Operator::RotateLeft)),
+              ("ROR", Apply(Operator::RotateRight))],
+            &[("mod", Apply(Operator::Modulo)),
+              ("div", Apply(Operator::IntegerDivision)),
+              ("(", Symbol('(')),
+              (")", Symbol(')'))],
+            &[(word_size.name(), NextWordSize),
+              ("UNS", ToggleUnsigned)],
+        ];
+        container(Self::buttons_grid(buttons))
+            .width(Length::Units(SCIENTIFIC_WIDTH))
+            .height(Length::Fill)
+            .align_y(alignment::Vertical::Bottom)
+            .padding(10)
+    }
+
+    /// A-F are digits in hexadecimal, some of them are commands otherwise
+    fn letter_command(&self, letter: char, modifiers: Modifiers) -> CalcMessage {
+        use CalcMessage::*;
+        if matches!(self.calc.borrow().settings().radix(),
+                    Some(Radix { base: Base::Hexadecimal, .. })) {
+            return Symbol(letter)
+        }
+        match letter {
+            'C' if modifiers.shift() => Apply(Operator::ArcCosine),
+            'C' => Cosine,
+            'E' => EulersNumber,
+            _ => Nothing
+        }
+    }
+
+    fn buttons_grid<'a>(buttons: &[&[(&'a str, CalcMessage)]]) -> Column<'a, CalcMessage> {
+        let rows: Vec<Element<CalcMessage>> = buttons.iter()
+            .map(|row| {
+                let row_buttons: Vec<Element<CalcMessage>> = row.iter()
+                    .map(|&(label, message)| button(Self::btn_text(label))
+                                                 .width(Length::FillPortion(1))
+                                                 .on_press(message)
+                                                 .into())
+                    .collect();
+                Row::with_children(row_buttons)
+                    .spacing(5)
+                    .into()
+            })
+            .collect();
+        Column::with_children(rows).spacing(5)
+    }
+
+    /// Past calculations, the most recent on top. Click on one loads its result.
+    fn tape_view(&self) -> Container<CalcMessage> {
+        let entries: Vec<Element<CalcMessage>> = self.calc.borrow()
+            .tape()
+            .entries()
+            .iter()
+            .rev()
+            .map(|entry| button(text(entry.to_string()).size(16))
+                             .width(Length::Fill)
+                             .style(theme::Button::Secondary)
+                             .on_press(CalcMessage::Set(entry.result))
+                             .into())
+            .collect();
+        if self.inspector {
+            return self.inspector_view()
+        }
+        let header: Row<CalcMessage> = row!(text("History")
+                                                .size(20)
+                                                .width(Length::Fill),
+                                            button(Self::btn_text("754"))
+                                                .on_press(CalcMessage::ToggleInspector),
+                                            button(Self::btn_text("CT"))
+                                                .on_press(CalcMessage::ClearTape))
+                                        .spacing(5);
+        let tape_col: Column<CalcMessage> = column!(
+            header,
+            scrollable(Column::with_children(entries).spacing(2))
+                .height(Length::Fill)
+        );
+        container(tape_col.spacing(5))
+            .width(Length::Units(TAPE_WIDTH))
+            .height(Length::Fill)
+            .padding(10)
+    }
+
+    /// Sign, exponent and mantissa of the current value in `f32` and `f64`
+    fn inspector_view(&self) -> Container<CalcMessage> {
+        let header: Row<CalcMessage> = row!(text("IEEE 754")
+                                                .size(20)
+                                                .width(Length::Fill),
+                                            button(Self::btn_text("H"))
+                                                .on_press(CalcMessage::ToggleInspector))
+                                        .spacing(5);
+        let calc: Ref<Calculator> = self.calc.borrow();
+        let sections: Vec<Element<CalcMessage>> = Precision::ALL.iter()
+            .map(|&precision| {
+                let description: String = match calc.inspect_float(precision) {
+                    Ok(inspection) => describe_float(&inspection),
+                    Err(err) => format!("{}\n{}", precision, err.to_string()),
+                };
+                text(description).size(14).into()
+            })
+            .collect();
+        let inspector_col: Column<CalcMessage> = column!(
+            header,
+            Column::with_children(sections).spacing(10)
+        );
+        container(inspector_col.spacing(5))
+            .width(Length::Units(TAPE_WIDTH))
+            .height(Length::Fill)
+            .padding(10)
+    }
+
+    /// Modes shown at the left of the display line
+    fn indicators(&self) -> String {
+        let calc: Ref<Calculator> = self.calc.borrow();
+        let mut indicators: Vec<String> = match calc.settings().radix() {
+            Some(radix) if radix.unsigned => vec![radix.base.to_string(), radix.word_size.to_string(), "UNS".into()],
+            Some(radix) => vec![radix.base.to_string(), radix.word_size.to_string()],
+            None => vec![calc.settings().angle_unit.to_string()],
+        };
+        if calc.settings().k_constant {
+            indicators.push("K".into());
+        }
+        if calc.settings().algebraic {
+            indicators.push("ALG".into());
+        }
+        if calc.settings().digits != DigitCapacity::default() {
+            indicators.push(calc.settings().digits.to_string());
+        }
+        if calc.settings().division_rounding == DivisionRounding::Truncated {
+            indicators.push(DivisionRounding::Truncated.to_string());
+        }
+        if self.hyperbolic {
+            indicators.push("HYP".into());
+        }
+        indicators.join("\n")
+    }
+
+    fn modified(&self, operator: Operator) -> Operator {
+        if self.hyperbolic {
+            operator.hyperbolic()
+        } else {
+            operator
+        }
+    }
+
+    /// Does nothing if the calculator hasn't changed since the last save
+    fn save_session(&mut self) {
+        let revision: u64 = self.calc.get_mut().revision();
+        if revision == self.saved_revision {
+            return
+        }
+        match storage::save_session(self.calc.get_mut()) {
+            Ok(_) => self.saved_revision = revision,
+            Err(err) => println!("ERROR: UNABLE TO SAVE SESSION: {}", err),
+        }
+    }
+
     fn btn_text(content: &str) -> Text<'_> {
         text(content)
             .width(Length::Shrink)
@@ -480,6 +950,7 @@ impl CalcState {
         if calc_response.is_ok() {
             let calc_ref: Ref<Calculator> = self.calc.borrow();
             let calc_ref: &Calculator = &calc_ref;
+            self.readout = base_readout(calc_ref.current_operand_to_dec(), calc_ref.settings().radix());
             use calculator::State::*;
             match &calc_ref.state {
                 ReadingLeftOrOperator(operand_cell) => {
@@ -495,17 +966,18 @@ impl CalcState {
                 } => {
                     self.write_operand_from_cell(operand_cell);
                 },
-                Result(result_cell) => {
+                Result(result_cell, _) => {
                     self.write_operand_from_cell(result_cell);
                 },
             }
             return;
         }
         self.line.get_mut().clear();
+        self.readout.clear();
         let line: &mut String = self.line.get_mut();
         line.push_str("e    "); // for e + M + space + '-' + '.'
         self.calc.get_mut().erase_all();
-        for _ in 0..calculator::BUFFER_SIZE - 1 {
+        for _ in 0..self.calc.get_mut().settings().capacity() - 1 {
             line.push(' ');
         }
         line.push('0');
@@ -519,7 +991,7 @@ impl CalcState {
         if !operand.has_dot() {
             line.push(' '); // for '.'
         }
-        let free_cells_count: usize = calculator::BUFFER_SIZE - operand.numbers_count();
+        let free_cells_count: usize = operand.capacity().saturating_sub(operand.numbers_count());
         for _ in 0..free_cells_count {
             line.push(' ');
         }
@@ -531,8 +1003,76 @@ impl CalcState {
         let operand_option: Ref<Option<Operand>> = cell.borrow();
         let operand: &Operand = operand_option.as_ref().unwrap();
         // let line: &mut String = &mut self.line.borrow_mut();
+        let nesting_depth: usize = self.calc.borrow().nesting_depth();
         self.line.borrow_mut().clear();
-        self.line.borrow_mut().push_str("   "); // for e + M + space
+        if nesting_depth > 0 {
+            // for '(' + depth
+            self.line.borrow_mut().push_str(&format!("({:<2}", nesting_depth));
+        } else {
+            self.line.borrow_mut().push_str("   "); // for e + M + space
+        }
         self.write_operand(operand);
     }
 }
+
+fn describe_float(inspection: &FloatInspection) -> String {
+    // the mantissa of f64 does not fit the panel in one line
+    const MANTISSA_LINE_LENGTH: usize = 26;
+    let bits: String = inspection.bits_string();
+    let mut parts = bits.split(' ');
+    let sign: &str = parts.next().unwrap_or_default();
+    let exponent: &str = parts.next().unwrap_or_default();
+    let mantissa: Vec<&str> = parts.next()
+        .unwrap_or_default()
+        .as_bytes()
+        .chunks(MANTISSA_LINE_LENGTH)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    format!("{} {}\nsign {}\nexp {} (2^{})\nmant {}\nerr {}",
+            inspection.precision,
+            inspection.hex(),
+            sign,
+            exponent,
+            inspection.unbiased_exponent(),
+            mantissa.join("\n     "),
+            inspection.error_string())
+}
+
+/// Lines of DEC, HEX, OCT and BIN digits of the integer, empty for fractions.
+/// The programmer mode shows the bits of the word, the other modes show the sign
+/// and the magnitude since the integers of 20 and more digits exceed 64 bits.
+fn base_readout(value: Decimal, radix: Option<Radix>) -> String {
+    let integer: Option<i128> = if value.fract().is_zero() {
+        value.to_i128()
+    } else {
+        None
+    };
+    let integer: i128 = match (integer, radix) {
+        (Some(integer), Some(radix)) => radix.wrap(integer),
+        (Some(integer), None) => integer,
+        (None, _) => return String::new()
+    };
+    Base::ALL.iter()
+        .map(|base| {
+            let digits: String = match radix {
+                _ if *base == Base::Decimal => integer.to_string(),
+                Some(radix) => base.format(radix.bit_pattern(integer).into()),
+                None if integer < 0 => format!("-{}", base.format(integer.unsigned_abs())),
+                None => base.format(integer.unsigned_abs()),
+            };
+            format!("{} {}", base, digits)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn hex_letter(key_code: KeyCode) -> char {
+    match key_code {
+        KeyCode::A => 'A',
+        KeyCode::B => 'B',
+        KeyCode::C => 'C',
+        KeyCode::D => 'D',
+        KeyCode::E => 'E',
+        _ => 'F',
+    }
+}
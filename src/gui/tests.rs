@@ -0,0 +1,32 @@
+use rust_decimal_macros::dec;
+
+use crate::calculator::settings::{Radix, Base, WordSize};
+
+use super::base_readout;
+
+#[test]
+fn base_readout_of_regular_mode() {
+    assert_eq!(base_readout(dec!(-255), None), "DEC -255\nHEX -FF\nOCT -377\nBIN -11111111");
+    // wider than the quad word
+    assert_eq!(base_readout(dec!(100000000000000000000), None).lines().nth(1),
+               Some("HEX 56BC75E2D63100000"));
+    assert_eq!(base_readout(dec!(2.5), None), "");
+    assert_eq!(base_readout(dec!(3.0), None).lines().next(), Some("DEC 3"));
+}
+
+#[test]
+fn base_readout_of_programmer_mode() {
+    let byte = Radix {
+        base: Base::Hexadecimal,
+        word_size: WordSize::Byte,
+        unsigned: false,
+    };
+    assert_eq!(base_readout(dec!(-1), Some(byte)), "DEC -1\nHEX FF\nOCT 377\nBIN 11111111");
+    // out of the word
+    assert_eq!(base_readout(dec!(200), Some(byte)), "DEC -56\nHEX C8\nOCT 310\nBIN 11001000");
+    let unsigned_byte = Radix {
+        unsigned: true,
+        ..byte
+    };
+    assert_eq!(base_readout(dec!(-1), Some(unsigned_byte)).lines().next(), Some("DEC 255"));
+}
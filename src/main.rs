@@ -4,8 +4,50 @@
 mod tests;
 mod calculator;
 mod gui;
+#[cfg(not(target_arch = "wasm32"))]
 mod clipboard;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+mod storage;
 
+#[cfg(target_arch = "wasm32")]
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     gui::run()
-}
\ No newline at end of file
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        attach_console();
+    }
+    match args.first().map(String::as_str) {
+        None => gui::run(),
+        Some("--repl") => cli::repl(),
+        Some("--eval") => match args.get(1) {
+            Some(keys) => cli::eval(keys),
+            None => Err("--eval requires a sequence of keys".into())
+        },
+        Some(arg) => Err(format!("Unknown argument: {}", arg).into())
+    }
+}
+
+/// The GUI subsystem starts without a console, so the terminal usage
+/// borrows the one of the parent shell or opens a new one
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+        fn AllocConsole() -> i32;
+    }
+    unsafe {
+        if AttachConsole(ATTACH_PARENT_PROCESS) == 0 {
+            AllocConsole();
+        }
+    }
+}
+
+#[cfg(all(not(windows), not(target_arch = "wasm32")))]
+fn attach_console() {}
@@ -0,0 +1,23 @@
+use std::{fs, path::PathBuf};
+
+use directories_next::ProjectDirs;
+
+const SESSION_FILE_NAME: &str = "session.json";
+
+pub(super) fn read_session() -> Option<String> {
+    fs::read_to_string(session_path()?).ok()
+}
+
+pub(super) fn write_session(content: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let path: PathBuf = session_path().ok_or("Unable to locate data directory")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+fn session_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "juodas-calc")
+        .map(|dirs| dirs.data_dir().join(SESSION_FILE_NAME))
+}
@@ -0,0 +1,23 @@
+use web_sys::Storage;
+
+const SESSION_KEY: &str = "juodas-calc.session";
+
+pub(super) fn read_session() -> Option<String> {
+    local_storage()?
+        .get_item(SESSION_KEY)
+        .ok()?
+}
+
+pub(super) fn write_session(content: &str) -> Result<(), Box<dyn std::error::Error>> {
+    local_storage()
+        .ok_or("localStorage is unavailable")?
+        .set_item(SESSION_KEY, content)
+        .map_err(|_| "Unable to write to localStorage")?;
+    Ok(())
+}
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?
+        .local_storage()
+        .ok()?
+}
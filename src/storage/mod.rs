@@ -0,0 +1,21 @@
+use crate::calculator::Calculator;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod file;
+#[cfg(target_arch = "wasm32")]
+mod local_storage;
+
+#[cfg(not(target_arch = "wasm32"))]
+use self::file as backend;
+#[cfg(target_arch = "wasm32")]
+use self::local_storage as backend;
+
+/// Restores the calculator saved on the previous exit
+pub fn load_session() -> Option<Calculator> {
+    let content: String = backend::read_session()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn save_session(calc: &Calculator) -> Result<(), Box<dyn std::error::Error>> {
+    backend::write_session(&serde_json::to_string(calc)?)
+}